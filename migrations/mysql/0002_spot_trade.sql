@@ -0,0 +1,11 @@
+-- 撮合成交，以交易对和撮合结果序列号去重
+CREATE TABLE IF NOT EXISTS spot_trade (
+    symbol        VARCHAR(64) NOT NULL,
+    seq_id        BIGINT NOT NULL,
+    buy_order_id  VARCHAR(64) NOT NULL,
+    sell_order_id VARCHAR(64) NOT NULL,
+    price         DECIMAL(36, 18) NOT NULL,
+    quantity      DECIMAL(36, 18) NOT NULL,
+    timestamp     BIGINT NOT NULL,
+    PRIMARY KEY (symbol, seq_id)
+);
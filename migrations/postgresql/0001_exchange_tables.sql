@@ -0,0 +1,66 @@
+-- 撮合依赖的业务表，已存在时跳过，可直接用于已有环境
+CREATE TABLE IF NOT EXISTS config_symbol_matching (
+    id      SERIAL PRIMARY KEY,
+    base    VARCHAR(32) NOT NULL,
+    quote   VARCHAR(32) NOT NULL,
+    is_open INTEGER NOT NULL DEFAULT 0,
+    server  VARCHAR(128) NOT NULL,
+    ctime   TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    mtime   TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+CREATE INDEX IF NOT EXISTS idx_config_symbol_matching_server ON config_symbol_matching (server);
+
+CREATE TABLE IF NOT EXISTS exchange_coin (
+    symbol             VARCHAR(64) PRIMARY KEY,
+    base_coin_scale    INTEGER NOT NULL DEFAULT 0,
+    base_symbol        VARCHAR(32),
+    coin_scale         INTEGER NOT NULL DEFAULT 0,
+    coin_symbol        VARCHAR(32),
+    enable             INTEGER NOT NULL DEFAULT 0,
+    fee                NUMERIC,
+    sort               INTEGER NOT NULL DEFAULT 0,
+    enable_market_buy  INTEGER NOT NULL DEFAULT 0,
+    enable_market_sell INTEGER NOT NULL DEFAULT 0,
+    min_sell_price     NUMERIC,
+    flag               INTEGER NOT NULL DEFAULT 0,
+    max_trading_order  INTEGER NOT NULL DEFAULT 0,
+    max_trading_time   INTEGER NOT NULL DEFAULT 0,
+    instrument         VARCHAR(32),
+    min_turnover       NUMERIC,
+    max_volume         NUMERIC,
+    min_volume         NUMERIC,
+    zone               INTEGER NOT NULL DEFAULT 0,
+    clear_time         VARCHAR(32),
+    end_time           VARCHAR(32),
+    publish_price      NUMERIC,
+    publish_type       INTEGER NOT NULL DEFAULT 0,
+    start_time         VARCHAR(32),
+    exchangeable       INTEGER NOT NULL DEFAULT 0,
+    publish_amount     NUMERIC,
+    visible            INTEGER NOT NULL DEFAULT 0,
+    max_buy_price      NUMERIC,
+    robot_type         INTEGER NOT NULL DEFAULT 0,
+    enable_buy         INTEGER NOT NULL DEFAULT 0,
+    enable_sell        INTEGER NOT NULL DEFAULT 0
+);
+
+CREATE TABLE IF NOT EXISTS exchange_order (
+    order_id       VARCHAR(64) PRIMARY KEY,
+    amount         NUMERIC,
+    base_symbol    VARCHAR(32),
+    canceled_time  BIGINT,
+    coin_symbol    VARCHAR(32),
+    completed_time BIGINT,
+    direction      INTEGER,
+    member_id      BIGINT,
+    price          NUMERIC,
+    status         INTEGER,
+    symbol         VARCHAR(64),
+    time           BIGINT,
+    traded_amount  NUMERIC,
+    turnover       NUMERIC,
+    order_type     INTEGER,
+    use_discount   VARCHAR(8),
+    order_resource INTEGER
+);
+CREATE INDEX IF NOT EXISTS idx_exchange_order_symbol_status ON exchange_order (symbol, status, time);
@@ -0,0 +1,21 @@
+-- 交易对租约与已收盘 K 线
+CREATE TABLE IF NOT EXISTS symbol_lease (
+    symbol     VARCHAR(64) PRIMARY KEY,
+    node_id    VARCHAR(128) NOT NULL,
+    expires_at BIGINT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS spot_kline (
+    symbol       VARCHAR(64) NOT NULL,
+    interval     VARCHAR(8) NOT NULL,
+    open_time    BIGINT NOT NULL,
+    close_time   BIGINT NOT NULL,
+    open         NUMERIC NOT NULL,
+    high         NUMERIC NOT NULL,
+    low          NUMERIC NOT NULL,
+    close        NUMERIC NOT NULL,
+    volume       NUMERIC NOT NULL,
+    quote_volume NUMERIC NOT NULL,
+    trade_count  BIGINT NOT NULL,
+    PRIMARY KEY (symbol, interval, open_time)
+);
@@ -0,0 +1,44 @@
+#!/usr/bin/env bash
+# 由 src/fbs 下的 .fbs 重新生成 *_generated.rs，修改 schema 后执行，生成的文件不要手工修改。
+# flatc 版本固定为 Cargo.toml 中 flatbuffers crate 的版本，不同版本生成的代码会有差异：
+#   https://github.com/google/flatbuffers/releases/tag/v24.3.25
+# 用法：
+#   scripts/generate_fbs.sh           重新生成
+#   scripts/generate_fbs.sh --check   只检查已提交的文件与 schema 是否一致，不一致时返回非 0
+# 可通过 FLATC 环境变量指定 flatc 路径
+set -euo pipefail
+
+FLATC_VERSION="24.3.25"
+FLATC="${FLATC:-flatc}"
+SCHEMAS=(order.fbs trade.fbs spot_log.fbs depth.fbs envelope.fbs)
+
+FBS_DIR="$(cd "$(dirname "$0")/../src/fbs" && pwd)"
+
+if ! command -v "$FLATC" >/dev/null 2>&1; then
+    echo "flatc not found, install flatc ${FLATC_VERSION} or set FLATC" >&2
+    exit 1
+fi
+actual_version="$("$FLATC" --version | awk '{print $NF}')"
+if [ "$actual_version" != "$FLATC_VERSION" ]; then
+    echo "flatc ${FLATC_VERSION} is required, found ${actual_version}" >&2
+    exit 1
+fi
+
+out_dir="$(mktemp -d)"
+trap 'rm -rf "$out_dir"' EXIT
+
+(cd "$FBS_DIR" && "$FLATC" --rust -o "$out_dir" "${SCHEMAS[@]}")
+# flatc 生成的 include 引用以 crate 根为起点，生成的模块实际位于 crate::fbs 下
+sed -i 's/use crate::\([a-z_]*_generated\)::\*;/use crate::fbs::\1::*;/' "$out_dir"/*_generated.rs
+
+if [ "${1:-}" = "--check" ]; then
+    status=0
+    for generated in "$out_dir"/*_generated.rs; do
+        if ! diff -u "$FBS_DIR/$(basename "$generated")" "$generated"; then
+            status=1
+        fi
+    done
+    exit "$status"
+fi
+
+cp "$out_dir"/*_generated.rs "$FBS_DIR"/
@@ -0,0 +1,301 @@
+use std::fmt;
+use std::time::Duration;
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use rdkafka::message::{Header, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::util::Timeout;
+use serde::{Deserialize, Serialize};
+use tklog::{async_error, async_info, ASYNC_LOG, LEVEL};
+use warp::http::StatusCode;
+use warp::reply::{self, Reply};
+use warp::Filter;
+
+use crate::config::MessageFormat;
+use crate::date::current_timestamp;
+use crate::envelope::Envelope;
+use crate::fbs::spot_log_generated::spot_log::{
+    Admin as FbsAdmin, AdminArgs, AdminCommand as FbsAdminCommand, LogLevel as FbsLogLevel,
+};
+use crate::spot_log::{LogType, SpotLog};
+use crate::supervisor::EngineStatuses;
+use crate::topic::Topic;
+
+// 写入管理指令的超时时间
+const PRODUCE_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 日志级别
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+    Off,
+}
+
+impl From<LogLevel> for FbsLogLevel {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => FbsLogLevel::Trace,
+            LogLevel::Debug => FbsLogLevel::Debug,
+            LogLevel::Info => FbsLogLevel::Info,
+            LogLevel::Warn => FbsLogLevel::Warn,
+            LogLevel::Error => FbsLogLevel::Error,
+            LogLevel::Fatal => FbsLogLevel::Fatal,
+            LogLevel::Off => FbsLogLevel::Off,
+        }
+    }
+}
+
+impl TryFrom<FbsLogLevel> for LogLevel {
+    type Error = &'static str;
+
+    fn try_from(level: FbsLogLevel) -> Result<Self, &'static str> {
+        match level {
+            FbsLogLevel::Trace => Ok(LogLevel::Trace),
+            FbsLogLevel::Debug => Ok(LogLevel::Debug),
+            FbsLogLevel::Info => Ok(LogLevel::Info),
+            FbsLogLevel::Warn => Ok(LogLevel::Warn),
+            FbsLogLevel::Error => Ok(LogLevel::Error),
+            FbsLogLevel::Fatal => Ok(LogLevel::Fatal),
+            FbsLogLevel::Off => Ok(LogLevel::Off),
+            _ => Err("Invalid log level in FlatBuffer data"),
+        }
+    }
+}
+
+impl From<LogLevel> for LEVEL {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Trace => LEVEL::Trace,
+            LogLevel::Debug => LEVEL::Debug,
+            LogLevel::Info => LEVEL::Info,
+            LogLevel::Warn => LEVEL::Warn,
+            LogLevel::Error => LEVEL::Error,
+            LogLevel::Fatal => LEVEL::Fatal,
+            LogLevel::Off => LEVEL::Off,
+        }
+    }
+}
+
+// 管理指令。与订单一起写入交易对的输入主题，由引擎按顺序执行，主备节点和重放时结果一致
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum AdminCommand {
+    //暂停撮合：拒绝新订单，撤单仍然处理
+    Halt,
+    //恢复撮合
+    Resume,
+    //撤销交易对的全部挂单，指定 user_id 时只撤销该用户的挂单
+    CancelAll { user_id: Option<u64> },
+    //立即写入快照
+    Snapshot,
+    //调整日志级别，作用于运行该交易对的整个进程
+    SetLogLevel { level: LogLevel },
+}
+
+impl fmt::Display for AdminCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AdminCommand::Halt => write!(f, "Halt"),
+            AdminCommand::Resume => write!(f, "Resume"),
+            AdminCommand::CancelAll { user_id: Some(user_id) } => write!(f, "CancelAll(user {})", user_id),
+            AdminCommand::CancelAll { user_id: None } => write!(f, "CancelAll"),
+            AdminCommand::Snapshot => write!(f, "Snapshot"),
+            AdminCommand::SetLogLevel { level } => write!(f, "SetLogLevel({:?})", level),
+        }
+    }
+}
+
+/// 一次管理操作，作为 LogType::Admin 的 SpotLog 写入输入主题，执行时原样发布到撮合结果主题留档
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdminAction {
+    #[serde(flatten)]
+    pub command: AdminCommand,
+    pub operator: String,         // 操作人
+    pub reason: Option<String>,
+    pub timestamp: u64,           // 毫秒级时间戳
+}
+
+impl AdminAction {
+    pub fn into_spot_log(self) -> SpotLog {
+        SpotLog {
+            log_type: LogType::Admin,
+            seq_id: 0,
+            order: None,
+            trade: None,
+            admin: Some(self),
+            report: None,
+        }
+    }
+
+    /// 将 FlatBuffers 中的 Admin 表转换成 AdminAction
+    pub fn from_flatbuffer(fbs_admin: &FbsAdmin) -> Result<Self, &'static str> {
+        let command = match fbs_admin.command() {
+            FbsAdminCommand::Halt => AdminCommand::Halt,
+            FbsAdminCommand::Resume => AdminCommand::Resume,
+            FbsAdminCommand::CancelAll => AdminCommand::CancelAll {
+                user_id: fbs_admin.has_user_id().then(|| fbs_admin.user_id()),
+            },
+            FbsAdminCommand::Snapshot => AdminCommand::Snapshot,
+            FbsAdminCommand::SetLogLevel => AdminCommand::SetLogLevel { level: fbs_admin.level().try_into()? },
+            _ => return Err("Invalid admin command in FlatBuffer data"),
+        };
+        Ok(AdminAction {
+            command,
+            operator: fbs_admin.operator().unwrap_or_default().to_string(),
+            reason: fbs_admin.reason().map(str::to_string),
+            timestamp: fbs_admin.timestamp(),
+        })
+    }
+
+    /// 在 builder 中写入 Admin 表，返回其偏移，供外层表引用
+    pub fn create_flatbuffer<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<FbsAdmin<'a>> {
+        let operator = builder.create_string(&self.operator);
+        let reason = self.reason.as_deref().map(|reason| builder.create_string(reason));
+        let mut args = AdminArgs {
+            operator: Some(operator),
+            reason,
+            timestamp: self.timestamp,
+            ..Default::default()
+        };
+        match &self.command {
+            AdminCommand::Halt => args.command = FbsAdminCommand::Halt,
+            AdminCommand::Resume => args.command = FbsAdminCommand::Resume,
+            AdminCommand::CancelAll { user_id } => {
+                args.command = FbsAdminCommand::CancelAll;
+                args.user_id = user_id.unwrap_or_default();
+                args.has_user_id = user_id.is_some();
+            }
+            AdminCommand::Snapshot => args.command = FbsAdminCommand::Snapshot,
+            AdminCommand::SetLogLevel { level } => {
+                args.command = FbsAdminCommand::SetLogLevel;
+                args.level = (*level).into();
+            }
+        }
+        FbsAdmin::create(builder, &args)
+    }
+}
+
+/// 调整本进程的日志级别
+#[allow(clippy::borrow_interior_mutable_const)]
+pub fn set_log_level(level: LogLevel) {
+    ASYNC_LOG.set_level(level.into());
+}
+
+/// 管理接口的状态
+#[derive(Clone)]
+pub struct AdminState {
+    pub statuses: EngineStatuses,
+    pub token: Option<String>,              // 未配置时不开放管理接口
+    pub producer: Option<FutureProducer>,
+}
+
+// 请求体，user_id 只用于 cancel，level 只用于 log-level
+#[derive(Debug, Deserialize)]
+struct AdminRequest {
+    operator: String,
+    reason: Option<String>,
+    user_id: Option<u64>,
+    level: Option<LogLevel>,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+fn error(status: StatusCode, message: String) -> reply::Response {
+    reply::with_status(reply::json(&ErrorBody { error: message }), status).into_response()
+}
+
+fn command(action: &str, request: &AdminRequest) -> Result<AdminCommand, String> {
+    match action {
+        "halt" => Ok(AdminCommand::Halt),
+        "resume" => Ok(AdminCommand::Resume),
+        "cancel" => Ok(AdminCommand::CancelAll { user_id: request.user_id }),
+        "snapshot" => Ok(AdminCommand::Snapshot),
+        "log-level" => request.level
+            .map(|level| AdminCommand::SetLogLevel { level })
+            .ok_or_else(|| "missing level".to_string()),
+        _ => Err(format!("unknown admin action {}", action)),
+    }
+}
+
+// 按常量时间比较令牌，耗时不随首个不同字节的位置变化，避免逐字节猜出令牌
+fn token_matches(authorization: Option<&str>, token: &str) -> bool {
+    let Some(provided) = authorization.and_then(|value| value.strip_prefix("Bearer ")) else {
+        return false;
+    };
+    if provided.len() != token.len() {
+        return false;
+    }
+    provided.bytes().zip(token.bytes()).fold(0u8, |diff, (a, b)| diff | (a ^ b)) == 0
+}
+
+async fn handle(
+    symbol: String,
+    action: String,
+    authorization: Option<String>,
+    request: AdminRequest,
+    state: AdminState,
+) -> Result<reply::Response, warp::Rejection> {
+    let Some(token) = &state.token else {
+        return Ok(error(StatusCode::NOT_FOUND, "admin api is disabled".to_string()));
+    };
+    if !token_matches(authorization.as_deref(), token) {
+        return Ok(error(StatusCode::UNAUTHORIZED, "invalid admin token".to_string()));
+    }
+
+    let symbol = symbol.replace('_', "/");
+    if !state.statuses.read().expect("engine statuses poisoned").contains_key(&symbol) {
+        return Ok(error(StatusCode::NOT_FOUND, format!("unknown symbol {}", symbol)));
+    }
+    let command = match command(&action, &request) {
+        Ok(command) => command,
+        Err(e) => return Ok(error(StatusCode::BAD_REQUEST, e)),
+    };
+    if request.operator.is_empty() {
+        return Ok(error(StatusCode::BAD_REQUEST, "missing operator".to_string()));
+    }
+    let Some(producer) = &state.producer else {
+        return Ok(error(StatusCode::SERVICE_UNAVAILABLE, "kafka producer is unavailable".to_string()));
+    };
+
+    let admin = AdminAction {
+        command,
+        operator: request.operator,
+        reason: request.reason,
+        timestamp: current_timestamp(),
+    };
+    let envelope = Envelope::new(&symbol, None, None, admin.clone().into_spot_log());
+    let payload = envelope.encode(MessageFormat::Json).expect("envelope is serializable");
+    let topic = format!("{}_{}", symbol.replace('/', "_"), Topic::SpotNewOrder);
+    // 标明编码格式，交易对的输入主题配置为 flatbuffers 时同样能够解码
+    let headers = OwnedHeaders::new().insert(Header { key: MessageFormat::HEADER, value: Some(MessageFormat::Json.as_str()) });
+    let record = FutureRecord::to(&topic).key(&symbol).payload(&payload).headers(headers);
+    match producer.send(record, Timeout::After(PRODUCE_TIMEOUT)).await {
+        Ok(_) => {
+            async_info!(format!("Admin {} on {} by {} queued", admin.command, symbol, admin.operator));
+            Ok(reply::with_status(reply::json(&admin), StatusCode::ACCEPTED).into_response())
+        }
+        Err((e, _)) => {
+            async_error!(format!("Failed to queue admin {} on {}: {}", admin.command, symbol, e));
+            Ok(error(StatusCode::BAD_GATEWAY, format!("failed to queue admin command: {}", e)))
+        }
+    }
+}
+
+/// 管理接口 `POST /admin/{symbol}/{halt|resume|cancel|snapshot|log-level}`，需携带 `Authorization: Bearer <admin_token>`。
+/// 指令写入交易对的输入主题后即返回 202，由引擎按顺序执行
+pub fn routes(state: AdminState) -> impl Filter<Extract = (reply::Response,), Error = warp::Rejection> + Clone {
+    warp::post()
+        .and(warp::path!("admin" / String / String))
+        .and(warp::header::optional::<String>("authorization"))
+        .and(warp::body::json::<AdminRequest>())
+        .and(warp::any().map(move || state.clone()))
+        .and_then(handle)
+}
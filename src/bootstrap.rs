@@ -0,0 +1,144 @@
+use std::collections::HashSet;
+use anyhow::{anyhow, Result};
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tklog::{async_error, async_info};
+use crate::db_pool::get_database;
+use crate::model::exchange_order::{ExchangeOrder, ExchangeOrderDirection, ExchangeOrderStatus, ExchangeOrderType};
+use crate::order::{Order, OrderType, Side};
+use crate::order_book::OrderBook;
+
+/// 将交易中的 exchange_order 转为订单簿中的挂单：剩余数量为委托数量减去已成交数量，
+/// 时间优先级取下单时间。市价单不挂单，不能恢复
+pub fn resting_order(row: &ExchangeOrder) -> Result<Order, String> {
+    let id = row.order_id.parse::<u64>()
+        .map_err(|_| format!("invalid order id {}", row.order_id))?;
+    let side = match row.direction {
+        Some(direction) if direction == ExchangeOrderDirection::Buy as i32 => Side::Buy,
+        Some(direction) if direction == ExchangeOrderDirection::Sell as i32 => Side::Sell,
+        direction => return Err(format!("unknown direction {:?}", direction)),
+    };
+    match row.order_type {
+        Some(order_type) if order_type == ExchangeOrderType::LimitPrice as i32 => {}
+        Some(order_type) if order_type == ExchangeOrderType::MarketPrice as i32 => {
+            return Err("market order cannot rest in the order book".to_string());
+        }
+        order_type => return Err(format!("unknown order type {:?}", order_type)),
+    }
+    let price = row.price
+        .and_then(|price| price.to_f64())
+        .filter(|price| *price > 0.0)
+        .ok_or_else(|| format!("invalid price {:?}", row.price))?;
+    let remaining = row.amount.unwrap_or_default() - row.traded_amount.unwrap_or_default();
+    if remaining <= Decimal::ZERO {
+        return Err(format!("no remaining quantity, amount {:?}, traded {:?}", row.amount, row.traded_amount));
+    }
+    let timestamp = row.time
+        .filter(|time| *time >= 0)
+        .ok_or_else(|| format!("invalid time {:?}", row.time))?;
+
+    Ok(Order {
+        id,
+        user_id: row.member_id.unwrap_or_default() as u64,
+        price,
+        quantity: remaining.to_f64().unwrap_or_default(),
+        timestamp: timestamp as u64,
+        order_type: OrderType::Limit,
+        side,
+        client_order_id: None,
+        filled_quantity: row.traded_amount.and_then(|traded| traded.to_f64()).unwrap_or_default(),
+    })
+}
+
+/// 按下单时间（相同时按订单号）排队，构造订单簿。无法恢复的订单跳过，连同原因一并返回；
+/// 买卖价格交叉说明数据与撮合结果不一致，返回错误
+pub fn build_order_book(rows: &[ExchangeOrder]) -> Result<(OrderBook, Vec<(String, String)>)> {
+    let mut skipped = Vec::new();
+    let mut orders = Vec::with_capacity(rows.len());
+    for row in rows {
+        match resting_order(row) {
+            Ok(order) => orders.push(order),
+            Err(reason) => skipped.push((row.order_id.clone(), reason)),
+        }
+    }
+    orders.sort_by_key(|order| (order.timestamp, order.id));
+
+    let (bids, asks): (Vec<Order>, Vec<Order>) = orders.into_iter().partition(|order| order.side == Side::Buy);
+    let order_book = OrderBook::restore(bids, asks);
+    if let (Some(best_bid), Some(best_ask)) = (order_book.best_bid(), order_book.best_ask()) {
+        if best_bid >= best_ask {
+            return Err(anyhow!("open orders are crossed: best bid {} >= best ask {}", best_bid, best_ask));
+        }
+    }
+    Ok((order_book, skipped))
+}
+
+/// 从 exchange_order 表中交易对交易中的订单恢复订单簿，用于从旧撮合迁移以及快照不可用时的恢复。
+/// 同时返回表中该交易对所有状态的订单号：之后仍从消费组已提交的 offset 继续消费输入主题，
+/// 重放到这些订单时其结果已在表中，撮合跳过，不会重复撮合已完成或已撤销的订单
+pub async fn load_order_book(symbol: &str) -> Result<(OrderBook, HashSet<u64>)> {
+    let db = get_database()?;
+    let rows = ExchangeOrder::get_orders_by_symbol_and_status_list(&db, symbol, vec![ExchangeOrderStatus::Trading as i32]).await?;
+    let known_orders = ExchangeOrder::get_order_ids_by_symbol(&db, symbol).await?
+        .iter()
+        .filter_map(|order_id| order_id.parse::<u64>().ok())
+        .collect::<HashSet<_>>();
+    let (order_book, skipped) = build_order_book(&rows)?;
+    for (order_id, reason) in &skipped {
+        async_error!(format!("Skip exchange_order {} of {}: {}", order_id, symbol, reason));
+    }
+    async_info!(format!(
+        "Bootstrapped {} from exchange_order: {} orders restored, {} skipped, {} known orders",
+        symbol, order_book.len(), skipped.len(), known_orders.len()
+    ));
+    Ok((order_book, known_orders))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(order_id: &str, direction: i32, price: i64, amount: i64, traded_amount: i64, time: i64) -> ExchangeOrder {
+        ExchangeOrder {
+            order_id: order_id.to_string(),
+            amount: Some(Decimal::from(amount)),
+            base_symbol: Some("USDT".to_string()),
+            canceled_time: None,
+            coin_symbol: Some("BTC".to_string()),
+            completed_time: None,
+            direction: Some(direction),
+            member_id: Some(7),
+            price: Some(Decimal::from(price)),
+            status: Some(ExchangeOrderStatus::Trading as i32),
+            symbol: Some("BTC/USDT".to_string()),
+            time: Some(time),
+            traded_amount: Some(Decimal::from(traded_amount)),
+            turnover: None,
+            order_type: Some(ExchangeOrderType::LimitPrice as i32),
+            use_discount: None,
+            order_resource: None,
+        }
+    }
+
+    #[test]
+    fn test_build_order_book_from_exchange_orders() {
+        let mut market = row("40", 0, 100, 1, 0, 1000);
+        market.order_type = Some(ExchangeOrderType::MarketPrice as i32);
+        let rows = vec![
+            row("30", 0, 100, 5, 2, 3000),
+            row("20", 0, 100, 1, 0, 2000),
+            row("10", 1, 101, 4, 0, 1000),
+            row("11", 1, 101, 1, 1, 1500),
+            market,
+        ];
+
+        let (order_book, skipped) = build_order_book(&rows).unwrap();
+        assert_eq!(skipped.iter().map(|(order_id, _)| order_id.as_str()).collect::<Vec<_>>(), vec!["11", "40"]);
+        let (bids, asks) = order_book.resting_orders();
+        // 同一价格按下单时间排队，数量为剩余数量
+        assert_eq!(bids.iter().map(|order| (order.id, order.quantity)).collect::<Vec<_>>(), vec![(20, 1.0), (30, 3.0)]);
+        assert_eq!(asks.iter().map(|order| (order.id, order.user_id)).collect::<Vec<_>>(), vec![(10, 7)]);
+
+        assert!(build_order_book(&[row("1", 0, 101, 1, 0, 1000), row("2", 1, 100, 1, 0, 1000)]).is_err());
+    }
+}
@@ -4,6 +4,8 @@ use serde_yaml::Error as YamlError;
 use std::fs;
 use std::path::Path;
 use std::sync::Arc;
+use crate::envelope::SCHEMA_VERSION;
+use crate::order::{Order, OrderType, RejectReason, Side};
 
 static GLOBAL_CONFIG: Lazy<Arc<Config>> = Lazy::new(|| {
     let config = Config::load().expect("加载配置失败");
@@ -15,6 +17,20 @@ pub struct Config {
     pub kafka_config: KafkaConfig,         // 替换 RabbitMQ 配置为 Kafka 配置
     pub mysql_config: Option<MysqlConfig>,
     pub postgresql_config: Option<PostgresqlConfig>,
+    #[serde(default)]
+    pub database_backend: DatabaseBackend,  // 业务表、租约和 K 线所在的数据库
+    #[serde(default)]
+    pub engine_config: EngineConfig,
+    #[serde(default)]
+    pub symbol_config: SymbolSourceConfig,
+    #[serde(default)]
+    pub node_config: NodeConfig,
+    #[serde(default)]
+    pub market_data_config: MarketDataConfig,
+    #[serde(default)]
+    pub http_config: HttpConfig,
+    #[serde(default)]
+    pub persistence_config: PersistenceConfig,
 }
 
 #[derive(Debug, Deserialize)]
@@ -32,6 +48,445 @@ pub struct PostgresqlConfig {
     pub url: String,
 }
 
+// 业务表（交易对配置、币对、订单及成交）所在的数据库
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DatabaseBackend {
+    #[default]
+    Postgresql,
+    Mysql,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct EngineConfig {
+    #[serde(default = "default_shutdown_timeout_ms")]
+    pub shutdown_timeout_ms: u64,         // 优雅关闭的最长等待时间（毫秒），超时后放弃排空
+    #[serde(default = "default_snapshot_dir")]
+    pub snapshot_dir: String,             // 订单簿快照保存目录
+    #[serde(default = "default_symbol_refresh_interval_ms")]
+    pub symbol_refresh_interval_ms: u64,  // 重新读取交易对配置的间隔（毫秒）
+    #[serde(default = "default_snapshot_interval_ms")]
+    pub snapshot_interval_ms: u64,        // 运行期间写入检查点快照的间隔（毫秒）
+    #[serde(default = "default_restart_max_attempts")]
+    pub restart_max_attempts: u32,        // 引擎异常后连续重启的最大次数，超过后标记为失败
+    #[serde(default = "default_restart_backoff_ms")]
+    pub restart_backoff_ms: u64,          // 首次重启前的等待时间（毫秒），之后每次翻倍
+    #[serde(default = "default_restart_backoff_max_ms")]
+    pub restart_backoff_max_ms: u64,      // 重启等待时间上限（毫秒）
+    #[serde(default = "default_stall_threshold_ms")]
+    pub stall_threshold_ms: u64,          // 撮合循环超过该时间（毫秒）没有运转时存活检查失败
+    #[serde(default)]
+    pub bootstrap_from_db: bool,          // 没有可用快照时从 exchange_order 表中交易中的订单恢复订单簿，重放输入主题时跳过表中已有的订单
+    #[serde(default)]
+    pub spot_log_format: MessageFormat,   // 输入订单和撮合结果的默认编码格式，可按交易对覆盖
+    #[serde(default = "default_output_schema_version")]
+    pub output_schema_version: u32,       // 撮合结果的消息结构版本，下游升级前可设为上一版本
+    #[serde(default = "default_client_order_window")]
+    pub client_order_window: usize,       // 每个交易对记住的最近客户端订单号个数，用于识别重复投递，0 表示不去重
+    #[serde(default)]
+    pub duplicate_policy: DuplicatePolicy, // 客户端订单号重复的新订单的处理方式
+}
+
+fn default_shutdown_timeout_ms() -> u64 {
+    30_000
+}
+
+fn default_snapshot_dir() -> String {
+    "snapshot".to_string()
+}
+
+fn default_symbol_refresh_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_snapshot_interval_ms() -> u64 {
+    5_000
+}
+
+fn default_restart_max_attempts() -> u32 {
+    5
+}
+
+fn default_restart_backoff_ms() -> u64 {
+    1_000
+}
+
+fn default_restart_backoff_max_ms() -> u64 {
+    60_000
+}
+
+fn default_stall_threshold_ms() -> u64 {
+    30_000
+}
+
+fn default_output_schema_version() -> u32 {
+    SCHEMA_VERSION
+}
+
+fn default_client_order_window() -> usize {
+    100_000
+}
+
+impl Default for EngineConfig {
+    fn default() -> Self {
+        EngineConfig {
+            shutdown_timeout_ms: default_shutdown_timeout_ms(),
+            snapshot_dir: default_snapshot_dir(),
+            symbol_refresh_interval_ms: default_symbol_refresh_interval_ms(),
+            snapshot_interval_ms: default_snapshot_interval_ms(),
+            restart_max_attempts: default_restart_max_attempts(),
+            restart_backoff_ms: default_restart_backoff_ms(),
+            restart_backoff_max_ms: default_restart_backoff_max_ms(),
+            stall_threshold_ms: default_stall_threshold_ms(),
+            bootstrap_from_db: false,
+            spot_log_format: MessageFormat::default(),
+            output_schema_version: default_output_schema_version(),
+            client_order_window: default_client_order_window(),
+            duplicate_policy: DuplicatePolicy::default(),
+        }
+    }
+}
+
+// 客户端订单号重复的新订单的处理方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DuplicatePolicy {
+    //回报 Rejected，不撮合
+    #[default]
+    Reject,
+    //直接丢弃，不产生任何撮合结果
+    Ignore,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NodeConfig {
+    pub node_id: Option<String>,          // 节点标识，可被环境变量 SPOT_MATCH_NODE_ID 或命令行 --node-id 覆盖
+    #[serde(default)]
+    pub lease_enabled: bool,              // 是否通过 symbol_lease 表保证交易对只由一个节点撮合，租约表位于 database_backend 指定的数据库
+    #[serde(default = "default_lease_ttl_ms")]
+    pub lease_ttl_ms: u64,                // 租约有效期（毫秒）
+    #[serde(default = "default_lease_renew_interval_ms")]
+    pub lease_renew_interval_ms: u64,     // 续约及等待租约时的重试间隔（毫秒），应明显小于有效期
+    #[serde(default)]
+    pub standby_enabled: bool,            // 未取得租约时以热备模式撮合但不发布，需同时开启 lease_enabled
+    #[serde(default = "default_standby_buffer_size")]
+    pub standby_buffer_size: usize,       // 热备节点缓存的未发布结果条数，用于接管时补发
+}
+
+fn default_lease_ttl_ms() -> u64 {
+    15_000
+}
+
+fn default_lease_renew_interval_ms() -> u64 {
+    3_000
+}
+
+fn default_standby_buffer_size() -> usize {
+    100_000
+}
+
+impl Default for NodeConfig {
+    fn default() -> Self {
+        NodeConfig {
+            node_id: None,
+            lease_enabled: false,
+            lease_ttl_ms: default_lease_ttl_ms(),
+            lease_renew_interval_ms: default_lease_renew_interval_ms(),
+            standby_enabled: false,
+            standby_buffer_size: default_standby_buffer_size(),
+        }
+    }
+}
+
+// 消息的编码格式，写入 Kafka 时同时写入 `format` 消息头
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MessageFormat {
+    #[default]
+    Json,
+    Flatbuffers,
+}
+
+impl MessageFormat {
+    /// Kafka 消息头名称
+    pub const HEADER: &'static str = "format";
+
+    /// 消息头中的取值，与配置文件中的写法一致
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MessageFormat::Json => "json",
+            MessageFormat::Flatbuffers => "flatbuffers",
+        }
+    }
+
+    /// 解析消息头中的取值，无法识别时返回 None
+    pub fn from_header(value: &[u8]) -> Option<Self> {
+        match value {
+            b"json" => Some(MessageFormat::Json),
+            b"flatbuffers" => Some(MessageFormat::Flatbuffers),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MarketDataConfig {
+    #[serde(default)]
+    pub depth_format: MessageFormat,      // 深度行情编码格式：json 或 flatbuffers
+    #[serde(default = "default_depth_snapshot_interval_ms")]
+    pub depth_snapshot_interval_ms: u64,  // 发布全量深度快照的间隔（毫秒）
+    #[serde(default)]
+    pub book_event_enabled: bool,         // 是否发布逐笔订单簿变化，数据量较大，默认关闭
+    #[serde(default = "default_ticker_interval_ms")]
+    pub ticker_interval_ms: u64,          // 发布行情及 24 小时统计的间隔（毫秒）
+    #[serde(default = "default_kline_interval_ms")]
+    pub kline_interval_ms: u64,           // 发布未收盘 K 线更新的间隔（毫秒），收盘由输入消息的时间决定
+    #[serde(default)]
+    pub kline_persist_enabled: bool,      // 是否将已收盘 K 线写入 database_backend 指定数据库的 spot_kline 表
+    #[serde(default = "default_recent_trades_size")]
+    pub recent_trades_size: usize,        // 查询接口保留的最近成交笔数
+}
+
+fn default_depth_snapshot_interval_ms() -> u64 {
+    10_000
+}
+
+fn default_ticker_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_kline_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_recent_trades_size() -> usize {
+    100
+}
+
+impl Default for MarketDataConfig {
+    fn default() -> Self {
+        MarketDataConfig {
+            depth_format: MessageFormat::default(),
+            depth_snapshot_interval_ms: default_depth_snapshot_interval_ms(),
+            book_event_enabled: false,
+            ticker_interval_ms: default_ticker_interval_ms(),
+            kline_interval_ms: default_kline_interval_ms(),
+            kline_persist_enabled: false,
+            recent_trades_size: default_recent_trades_size(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HttpConfig {
+    #[serde(default)]
+    pub enabled: bool,                    // 是否启动查询接口
+    #[serde(default = "default_http_bind_address")]
+    pub bind_address: String,             // 监听地址，例如 "0.0.0.0:8080"
+    #[serde(default = "default_ws_buffer_size")]
+    pub ws_buffer_size: usize,            // 每个交易对推送队列的长度，WebSocket 客户端落后超过该长度时重新同步
+    #[serde(default)]
+    pub admin_token: Option<String>,      // 管理接口令牌，未配置时不开放管理接口
+}
+
+fn default_http_bind_address() -> String {
+    "0.0.0.0:8080".to_string()
+}
+
+fn default_ws_buffer_size() -> usize {
+    1024
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        HttpConfig {
+            enabled: false,
+            bind_address: default_http_bind_address(),
+            ws_buffer_size: default_ws_buffer_size(),
+            admin_token: None,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PersistenceConfig {
+    #[serde(default)]
+    pub enabled: bool,                    // 是否将成交写入 spot_trade 表并更新 exchange_order
+    #[serde(default = "default_persistence_batch_size")]
+    pub batch_size: usize,                // 每个事务最多写入的事件数
+    #[serde(default = "default_persistence_flush_interval_ms")]
+    pub flush_interval_ms: u64,           // 凑批等待的最长时间（毫秒）
+    #[serde(default = "default_persistence_queue_size")]
+    pub queue_size: usize,                // 每个交易对待写入队列的长度，队列占满后事件落盘到快照目录，撮合不等待
+}
+
+fn default_persistence_batch_size() -> usize {
+    500
+}
+
+fn default_persistence_flush_interval_ms() -> u64 {
+    100
+}
+
+fn default_persistence_queue_size() -> usize {
+    100_000
+}
+
+impl Default for PersistenceConfig {
+    fn default() -> Self {
+        PersistenceConfig {
+            enabled: false,
+            batch_size: default_persistence_batch_size(),
+            flush_interval_ms: default_persistence_flush_interval_ms(),
+            queue_size: default_persistence_queue_size(),
+        }
+    }
+}
+
+// 交易对列表来源
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SymbolSource {
+    //从 config_symbol_matching 表读取
+    #[default]
+    Db,
+    //从配置文件 symbols 列表读取
+    File,
+    //两者合并，同一交易对以配置文件为准
+    Both,
+}
+
+impl SymbolSource {
+    pub fn uses_db(&self) -> bool {
+        matches!(self, SymbolSource::Db | SymbolSource::Both)
+    }
+
+    pub fn uses_file(&self) -> bool {
+        matches!(self, SymbolSource::File | SymbolSource::Both)
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct SymbolSourceConfig {
+    #[serde(default)]
+    pub source: SymbolSource,             // db、file 或 both
+    #[serde(default)]
+    pub symbols: Vec<SymbolConfig>,       // source 为 file 或 both 时使用的交易对列表
+}
+
+// 单个交易对配置
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SymbolConfig {
+    pub base: String,
+    pub quote: String,
+    #[serde(default = "default_is_open")]
+    pub is_open: i32,                     // 1 开放撮合，其它值不启动
+    #[serde(default)]
+    pub rules: SymbolRules,
+    #[serde(default)]
+    pub spot_log_format: Option<MessageFormat>,  // 覆盖 engine_config.spot_log_format
+}
+
+fn default_is_open() -> i32 {
+    1
+}
+
+impl SymbolConfig {
+    pub fn symbol(&self) -> String {
+        self.base.clone() + "/" + &self.quote
+    }
+}
+
+// 交易规则，未配置的项不做限制
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SymbolRules {
+    pub price_scale: Option<u32>,         // 价格小数位数
+    pub quantity_scale: Option<u32>,      // 数量小数位数
+    pub min_quantity: Option<f64>,        // 最小下单数量
+    pub max_quantity: Option<f64>,        // 最大下单数量
+    pub min_turnover: Option<f64>,        // 限价单最小成交额（价格 * 数量）
+    #[serde(default = "default_true")]
+    pub enable_market_buy: bool,
+    #[serde(default = "default_true")]
+    pub enable_market_sell: bool,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Default for SymbolRules {
+    fn default() -> Self {
+        SymbolRules {
+            price_scale: None,
+            quantity_scale: None,
+            min_quantity: None,
+            max_quantity: None,
+            min_turnover: None,
+            enable_market_buy: true,
+            enable_market_sell: true,
+        }
+    }
+}
+
+impl SymbolRules {
+    /// 校验订单是否符合交易规则，不符合时返回原因
+    pub fn validate(&self, order: &Order) -> Result<(), RejectReason> {
+        if !order.quantity.is_finite() || order.quantity <= 0.0 {
+            return Err(RejectReason::InvalidQuantity(order.quantity));
+        }
+
+        match order.order_type {
+            OrderType::Market => {
+                if order.side == Side::Buy && !self.enable_market_buy {
+                    return Err(RejectReason::MarketBuyDisabled);
+                }
+                if order.side == Side::Sell && !self.enable_market_sell {
+                    return Err(RejectReason::MarketSellDisabled);
+                }
+            }
+            OrderType::Limit => {
+                if !order.price.is_finite() || order.price <= 0.0 {
+                    return Err(RejectReason::InvalidPrice(order.price));
+                }
+                if let Some(scale) = self.price_scale {
+                    if !fits_scale(order.price, scale) {
+                        return Err(RejectReason::PriceScale { price: order.price, scale });
+                    }
+                }
+                if let Some(min_turnover) = self.min_turnover {
+                    if order.price * order.quantity < min_turnover {
+                        return Err(RejectReason::TurnoverBelowMinimum(min_turnover));
+                    }
+                }
+            }
+        }
+
+        if let Some(scale) = self.quantity_scale {
+            if !fits_scale(order.quantity, scale) {
+                return Err(RejectReason::QuantityScale { quantity: order.quantity, scale });
+            }
+        }
+        if let Some(min_quantity) = self.min_quantity {
+            if order.quantity < min_quantity {
+                return Err(RejectReason::QuantityBelowMinimum(min_quantity));
+            }
+        }
+        if let Some(max_quantity) = self.max_quantity {
+            if order.quantity > max_quantity {
+                return Err(RejectReason::QuantityAboveMaximum(max_quantity));
+            }
+        }
+        Ok(())
+    }
+}
+
+// 判断数值在给定小数位数下是否没有多余精度
+fn fits_scale(value: f64, scale: u32) -> bool {
+    let scaled = value * 10f64.powi(scale as i32);
+    (scaled - scaled.round()).abs() < 1e-6
+}
+
 impl Config {
     /// 从指定路径加载配置文件。如果找不到配置文件，则加载默认配置
     pub fn load() -> Result<Self, YamlError> {
@@ -75,3 +530,37 @@ fn example_usage() {
         println!("PostgreSQL 配置不存在");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_symbol_config_from_yaml() {
+        let config: Config = serde_yaml::from_str(r#"
+kafka_config:
+  brokers: "localhost:9092"
+symbol_config:
+  source: "file"
+  symbols:
+    - base: "BTC"
+      quote: "USDT"
+      rules:
+        quantity_scale: 2
+        enable_market_buy: false
+"#).unwrap();
+
+        assert_eq!(config.symbol_config.source, SymbolSource::File);
+        assert_eq!(config.engine_config.shutdown_timeout_ms, 30_000);
+
+        let symbol_config = &config.symbol_config.symbols[0];
+        assert_eq!(symbol_config.symbol(), "BTC/USDT");
+        assert_eq!(symbol_config.is_open, 1);
+        assert!(symbol_config.rules.enable_market_sell);
+
+        let rules = &symbol_config.rules;
+        assert!(rules.validate(&Order::new(1, 100.0, 1.25, OrderType::Limit, Side::Buy)).is_ok());
+        assert!(rules.validate(&Order::new(1, 100.0, 1.255, OrderType::Limit, Side::Buy)).is_err());
+        assert!(rules.validate(&Order::new(1, 0.0, 1.0, OrderType::Market, Side::Buy)).is_err());
+    }
+}
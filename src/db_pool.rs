@@ -2,7 +2,7 @@ use once_cell::sync::Lazy;
 use sqlx::{Error, MySql, Pool, PgPool};
 use sqlx::mysql::MySqlPoolOptions;
 use sqlx::postgres::PgPoolOptions;
-use crate::config::Config;
+use crate::config::{Config, DatabaseBackend};
 
 // MySQL 连接池
 static MYSQL_POOL: Lazy<Option<Pool<MySql>>> = Lazy::new(|| {
@@ -65,3 +65,113 @@ pub async fn get_postgresql_connection() -> Result<sqlx::pool::PoolConnection<sq
     let conn = pool.acquire().await?;
     Ok(conn)
 }
+
+/// 业务表所在数据库的连接池，按配置的后端选择。查询统一以 `?` 作为占位符书写，
+/// 在 PostgreSQL 上执行前转换为 `$1, $2, ...`
+#[derive(Debug, Clone)]
+pub enum Database {
+    Postgresql(PgPool),
+    Mysql(Pool<MySql>),
+}
+
+/// 业务表所在数据库上的事务
+pub enum DatabaseTransaction {
+    Postgresql(Box<sqlx::Transaction<'static, sqlx::Postgres>>),
+    Mysql(Box<sqlx::Transaction<'static, MySql>>),
+}
+
+impl Database {
+    pub fn backend(&self) -> DatabaseBackend {
+        match self {
+            Database::Postgresql(_) => DatabaseBackend::Postgresql,
+            Database::Mysql(_) => DatabaseBackend::Mysql,
+        }
+    }
+
+    pub async fn begin(&self) -> Result<DatabaseTransaction, Error> {
+        match self {
+            Database::Postgresql(pool) => Ok(DatabaseTransaction::Postgresql(Box::new(pool.begin().await?))),
+            Database::Mysql(pool) => Ok(DatabaseTransaction::Mysql(Box::new(pool.begin().await?))),
+        }
+    }
+}
+
+impl DatabaseTransaction {
+    pub async fn commit(self) -> Result<(), Error> {
+        match self {
+            DatabaseTransaction::Postgresql(tx) => tx.commit().await,
+            DatabaseTransaction::Mysql(tx) => tx.commit().await,
+        }
+    }
+}
+
+// 获取业务表所在数据库的连接池，对应的数据库未配置时返回Err
+pub fn get_database() -> Result<Database, Error> {
+    match Config::global().database_backend {
+        DatabaseBackend::Postgresql => Ok(Database::Postgresql(get_postgresql_pool()?.clone())),
+        DatabaseBackend::Mysql => Ok(Database::Mysql(get_mysql_pool()?.clone())),
+    }
+}
+
+/// 将 `?` 占位符转换为目标后端的写法
+pub fn placeholders(backend: DatabaseBackend, query: &str) -> String {
+    match backend {
+        DatabaseBackend::Mysql => query.to_string(),
+        DatabaseBackend::Postgresql => {
+            let mut converted = String::with_capacity(query.len() + 8);
+            let mut index = 0;
+            for c in query.chars() {
+                if c == '?' {
+                    index += 1;
+                    converted.push('$');
+                    converted.push_str(&index.to_string());
+                } else {
+                    converted.push(c);
+                }
+            }
+            converted
+        }
+    }
+}
+
+/// `n` 个以逗号分隔的 `?`，用于 IN 列表
+pub fn in_list(n: usize) -> String {
+    vec!["?"; n].join(", ")
+}
+
+/// 在连接池上执行查询：按后端展开为两个分支，`$sql` 为已转换占位符的语句，
+/// `$pool` 为对应后端的连接池，两个分支分别做类型检查
+#[macro_export]
+macro_rules! on_database {
+    ($db:expr, $query:expr, |$pool:ident, $sql:ident| $body:expr) => {
+        match $db {
+            $crate::db_pool::Database::Postgresql($pool) => {
+                let $sql = $crate::db_pool::placeholders($crate::config::DatabaseBackend::Postgresql, &$query);
+                $body
+            }
+            $crate::db_pool::Database::Mysql($pool) => {
+                let $sql = $crate::db_pool::placeholders($crate::config::DatabaseBackend::Mysql, &$query);
+                $body
+            }
+        }
+    };
+}
+
+/// 在事务上执行查询，`$conn` 为对应后端的连接
+#[macro_export]
+macro_rules! on_transaction {
+    ($tx:expr, $query:expr, |$conn:ident, $sql:ident| $body:expr) => {
+        match $tx {
+            $crate::db_pool::DatabaseTransaction::Postgresql(tx) => {
+                let $conn = &mut ***tx;
+                let $sql = $crate::db_pool::placeholders($crate::config::DatabaseBackend::Postgresql, &$query);
+                $body
+            }
+            $crate::db_pool::DatabaseTransaction::Mysql(tx) => {
+                let $conn = &mut ***tx;
+                let $sql = $crate::db_pool::placeholders($crate::config::DatabaseBackend::Mysql, &$query);
+                $body
+            }
+        }
+    };
+}
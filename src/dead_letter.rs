@@ -0,0 +1,197 @@
+use std::fmt;
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use rdkafka::config::ClientConfig;
+use rdkafka::consumer::{BaseConsumer, Consumer};
+use rdkafka::message::{Header, Headers, OwnedHeaders};
+use rdkafka::producer::{BaseProducer, BaseRecord, Producer};
+use rdkafka::{Message, Offset, TopicPartitionList};
+use crate::date::current_timestamp;
+use crate::topic::Topic;
+
+// 死信消息头，原始消息头之外追加，重新注入时去掉
+const HEADER_PREFIX: &str = "dead_letter_";
+pub const REASON_HEADER: &str = "dead_letter_reason";
+pub const ERROR_HEADER: &str = "dead_letter_error";
+pub const SOURCE_TOPIC_HEADER: &str = "dead_letter_source_topic";
+pub const SOURCE_PARTITION_HEADER: &str = "dead_letter_source_partition";
+pub const SOURCE_OFFSET_HEADER: &str = "dead_letter_source_offset";
+pub const TIMESTAMP_HEADER: &str = "dead_letter_timestamp";
+
+// 读取死信主题时单次等待消息的时间
+const POLL_TIMEOUT: Duration = Duration::from_secs(5);
+
+// 进入死信主题的原因
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeadLetterReason {
+    //无法解码：格式错误、版本不支持或消息体为空
+    Malformed,
+    //输入主题不接受的消息类型，或消息类型缺少对应的内容
+    UnknownType,
+    //未通过校验：交易对不符、暂停撮合、订单号重复或不符合交易规则
+    ValidationFailed,
+}
+
+impl DeadLetterReason {
+    /// 消息头及指标中使用的取值
+    pub fn label(&self) -> &'static str {
+        match self {
+            DeadLetterReason::Malformed => "malformed",
+            DeadLetterReason::UnknownType => "unknown_type",
+            DeadLetterReason::ValidationFailed => "validation_failed",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label {
+            "malformed" => Some(DeadLetterReason::Malformed),
+            "unknown_type" => Some(DeadLetterReason::UnknownType),
+            "validation_failed" => Some(DeadLetterReason::ValidationFailed),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DeadLetterReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.label())
+    }
+}
+
+/// 交易对的死信主题
+pub fn topic(symbol: &str) -> String {
+    format!("{}_{}", symbol.replace('/', "_"), Topic::SpotDeadLetter)
+}
+
+/// 无法撮合的输入消息。原始字节和消息头原样写入死信主题，原因及来源位置写入 dead_letter_* 消息头
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub reason: DeadLetterReason,
+    pub error: String,
+    pub source_topic: String,
+    pub partition: i32,
+    pub offset: i64,
+    pub headers: Option<OwnedHeaders>,  // 原始消息头
+    pub payload: Vec<u8>,               // 原始字节
+}
+
+impl DeadLetter {
+    /// 写入死信主题的消息头：原始消息头加上死信原因和来源位置
+    pub fn headers(&self) -> OwnedHeaders {
+        let partition = self.partition.to_string();
+        let offset = self.offset.to_string();
+        let timestamp = current_timestamp().to_string();
+        original_headers(self.headers.as_ref())
+            .insert(Header { key: REASON_HEADER, value: Some(self.reason.label()) })
+            .insert(Header { key: ERROR_HEADER, value: Some(&self.error) })
+            .insert(Header { key: SOURCE_TOPIC_HEADER, value: Some(&self.source_topic) })
+            .insert(Header { key: SOURCE_PARTITION_HEADER, value: Some(&partition) })
+            .insert(Header { key: SOURCE_OFFSET_HEADER, value: Some(&offset) })
+            .insert(Header { key: TIMESTAMP_HEADER, value: Some(&timestamp) })
+    }
+}
+
+/// 去掉 dead_letter_* 消息头，还原为写入输入主题时的消息头
+pub fn original_headers<H: Headers>(headers: Option<&H>) -> OwnedHeaders {
+    let mut original = OwnedHeaders::new();
+    for header in headers.into_iter().flat_map(|headers| headers.iter()) {
+        if !header.key.starts_with(HEADER_PREFIX) {
+            original = original.insert(header);
+        }
+    }
+    original
+}
+
+/// 将死信主题 0 号分区从 `from_offset` 起的消息原样写回交易对的输入主题，
+/// 指定 `reason` 时只写回该原因的消息，最多写回 `limit` 条，返回写回的条数。
+/// 应在修复生产者或交易规则后执行，写回的消息仍不合法时会再次进入死信主题。
+/// 阻塞调用，异步上下文中需放在 spawn_blocking 中执行
+pub fn reinject(
+    brokers: &str,
+    symbol: &str,
+    from_offset: i64,
+    limit: Option<usize>,
+    reason: Option<DeadLetterReason>,
+) -> Result<usize> {
+    let dead_letter_topic = topic(symbol);
+    let input_topic = format!("{}_{}", symbol.replace('/', "_"), Topic::SpotNewOrder);
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", format!("{}_reinject", dead_letter_topic))
+        .set("enable.auto.commit", "false")
+        .create()?;
+    let producer: BaseProducer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .create()?;
+
+    let (low, high) = consumer.fetch_watermarks(&dead_letter_topic, 0, POLL_TIMEOUT)?;
+    let start = from_offset.max(low);
+    let mut assignment = TopicPartitionList::new();
+    assignment.add_partition_offset(&dead_letter_topic, 0, Offset::Offset(start))?;
+    consumer.assign(&assignment)?;
+
+    let limit = limit.unwrap_or(usize::MAX);
+    let mut reinjected = 0;
+    let mut next_offset = start;
+    while next_offset < high && reinjected < limit {
+        let message = consumer.poll(POLL_TIMEOUT)
+            .ok_or_else(|| anyhow!("Timed out reading {} at offset {}", dead_letter_topic, next_offset))??;
+        next_offset = message.offset() + 1;
+
+        let headers = message.headers();
+        let message_reason = headers
+            .and_then(|headers| headers.iter().find(|header| header.key == REASON_HEADER))
+            .and_then(|header| header.value)
+            .and_then(|value| DeadLetterReason::from_label(&String::from_utf8_lossy(value)));
+        if reason.is_some() && message_reason != reason {
+            continue;
+        }
+
+        let mut record = BaseRecord::to(&input_topic)
+            .payload(message.payload().unwrap_or_default())
+            .headers(original_headers(headers.map(|headers| headers.detach()).as_ref()));
+        if let Some(key) = message.key() {
+            record = record.key(key);
+        }
+        producer.send(record).map_err(|(e, _)| e)?;
+        producer.poll(Duration::ZERO);
+        reinjected += 1;
+    }
+
+    producer.flush(POLL_TIMEOUT)?;
+    Ok(reinjected)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::MessageFormat;
+
+    #[test]
+    fn test_dead_letter_headers() {
+        let dead_letter = DeadLetter {
+            reason: DeadLetterReason::ValidationFailed,
+            error: "quantity 0.5 below minimum 1".to_string(),
+            source_topic: "BTC_USDT_SpotNewOrder".to_string(),
+            partition: 0,
+            offset: 42,
+            headers: Some(OwnedHeaders::new().insert(Header { key: MessageFormat::HEADER, value: Some("flatbuffers") })),
+            payload: vec![1, 2, 3],
+        };
+
+        let headers = dead_letter.headers();
+        let value = |key: &str| headers.iter()
+            .find(|header| header.key == key)
+            .and_then(|header| header.value)
+            .map(|value| String::from_utf8_lossy(value).to_string());
+        assert_eq!(value(REASON_HEADER).as_deref(), Some("validation_failed"));
+        assert_eq!(value(SOURCE_OFFSET_HEADER).as_deref(), Some("42"));
+        assert_eq!(value(MessageFormat::HEADER).as_deref(), Some("flatbuffers"));
+
+        // 重新注入时只保留原始消息头
+        let original = original_headers(Some(&headers));
+        assert_eq!(original.count(), 1);
+        assert_eq!(original.get(0).key, MessageFormat::HEADER);
+        assert_eq!(DeadLetterReason::from_label(DeadLetterReason::UnknownType.label()), Some(DeadLetterReason::UnknownType));
+    }
+}
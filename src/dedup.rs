@@ -0,0 +1,117 @@
+use std::collections::{HashSet, VecDeque};
+use crate::order::Order;
+
+/// 去重窗口中的一条记录：(用户, 客户端订单号)，客户端订单号只需在同一用户内唯一
+pub type ClientOrderKey = (u64, String);
+
+/// 最近受理的带客户端订单号的新订单，Kafka 重复投递同一订单时据此识别。
+/// 超出容量后淘汰最早受理的记录，容量为 0 时不去重。随快照持久化，重启和重放后结果不变
+#[derive(Debug, Clone, Default)]
+pub struct ClientOrderWindow {
+    capacity: usize,
+    keys: HashSet<ClientOrderKey>,
+    queue: VecDeque<ClientOrderKey>,  // 按受理顺序排列，用于淘汰
+}
+
+impl ClientOrderWindow {
+    pub fn new(capacity: usize) -> Self {
+        ClientOrderWindow { capacity, ..Default::default() }
+    }
+
+    /// 从快照中按受理顺序排列的记录恢复，容量由 `set_capacity` 设置
+    pub fn restore(entries: Vec<ClientOrderKey>) -> Self {
+        ClientOrderWindow {
+            capacity: entries.len(),
+            keys: entries.iter().cloned().collect(),
+            queue: entries.into(),
+        }
+    }
+
+    fn key(order: &Order) -> Option<ClientOrderKey> {
+        order.client_order_id.clone().map(|client_order_id| (order.user_id, client_order_id))
+    }
+
+    /// 调整容量，容量变小时只保留最近受理的记录
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        self.evict();
+    }
+
+    fn evict(&mut self) {
+        while self.queue.len() > self.capacity {
+            if let Some(key) = self.queue.pop_front() {
+                self.keys.remove(&key);
+            }
+        }
+    }
+
+    /// 订单的客户端订单号是否已在窗口中，没有客户端订单号的订单不参与去重
+    pub fn contains(&self, order: &Order) -> bool {
+        Self::key(order).is_some_and(|key| self.keys.contains(&key))
+    }
+
+    /// 记录受理的新订单
+    pub fn insert(&mut self, order: &Order) {
+        if self.capacity == 0 {
+            return;
+        }
+        if let Some(key) = Self::key(order).filter(|key| !self.keys.contains(key)) {
+            self.keys.insert(key.clone());
+            self.queue.push_back(key);
+            self.evict();
+        }
+    }
+
+    /// 按受理顺序导出全部记录，用于生成快照
+    pub fn entries(&self) -> Vec<ClientOrderKey> {
+        self.queue.iter().cloned().collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{OrderType, Side};
+
+    fn order(user_id: u64, client_order_id: Option<&str>) -> Order {
+        Order {
+            client_order_id: client_order_id.map(str::to_string),
+            ..Order::new(user_id, 100.0, 1.0, OrderType::Limit, Side::Buy)
+        }
+    }
+
+    #[test]
+    fn test_client_order_window() {
+        let mut window = ClientOrderWindow::new(2);
+        window.insert(&order(1, Some("a")));
+        window.insert(&order(1, None));
+        assert!(window.contains(&order(1, Some("a"))));
+        // 不同用户可以使用相同的客户端订单号，没有客户端订单号的订单不去重
+        assert!(!window.contains(&order(2, Some("a"))));
+        assert!(!window.contains(&order(1, None)));
+
+        // 超出容量后淘汰最早的记录
+        window.insert(&order(2, Some("a")));
+        window.insert(&order(1, Some("b")));
+        assert_eq!(window.entries(), vec![(2, "a".to_string()), (1, "b".to_string())]);
+        assert!(!window.contains(&order(1, Some("a"))));
+
+        // 从快照恢复后仍能识别重复订单，容量变小时保留最近的记录
+        let mut restored = ClientOrderWindow::restore(window.entries());
+        restored.set_capacity(1);
+        assert!(restored.contains(&order(1, Some("b"))));
+        assert!(!restored.contains(&order(2, Some("a"))));
+
+        let mut disabled = ClientOrderWindow::new(0);
+        disabled.insert(&order(1, Some("a")));
+        assert!(disabled.is_empty());
+    }
+}
@@ -1,134 +1,1287 @@
+use std::collections::{HashSet, VecDeque};
+use std::fmt;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use rdkafka::consumer::Consumer;
-use rdkafka::message::BorrowedMessage;
-use rdkafka::Message;
+use std::time::{Duration, Instant};
+use rdkafka::consumer::{CommitMode, Consumer};
+use rdkafka::message::{BorrowedMessage, Header, Headers, OwnedHeaders};
+use rdkafka::producer::{FutureProducer, FutureRecord, Producer};
+use rdkafka::util::Timeout;
+use rdkafka::{Message, Offset};
 use tklog::{async_error, async_info};
-use tokio::sync::{mpsc, Mutex};
-use anyhow::Result;
+use tokio::sync::{mpsc, watch, Mutex};
+use tokio::task::JoinHandle;
+use anyhow::{anyhow, Result};
+use serde::{Deserialize, Serialize};
 
-use crate::config::Config;
-use crate::kafka::{create_consumer};
-use crate::order_book::OrderBook;
-use crate::spot_log::SpotLog;
+use crate::admin::{set_log_level, AdminCommand};
+use crate::bootstrap::load_order_book;
+use crate::config::{Config, DuplicatePolicy, MessageFormat, SymbolRules};
+use crate::date::current_timestamp;
+use crate::dead_letter::{self, DeadLetter, DeadLetterReason};
+use crate::dedup::ClientOrderWindow;
+use crate::envelope::{Envelope, MIN_SCHEMA_VERSION, SCHEMA_VERSION};
+use crate::kafka::{create_consumer, create_producer, fetch_last_payloads, fetch_partitions, LoggingConsumer};
+use crate::health::{EngineHealth, EnginePhase};
+use crate::kline::{Kline, KlineAggregator, KlineWriter};
+use crate::market_data::{DepthKind, DepthUpdate, MarketDataStore, OrderBookEvent};
+use crate::metrics::{self, SymbolMetrics};
+use crate::node::node_id;
+use crate::execution::{ExecutionReport, ExecutionStatus};
+use crate::order::{Order, RejectReason};
+use crate::order_book::{BookEvent, Depth, OrderBook};
+use crate::persistence::{PersistEvent, PersistenceSink};
+use crate::snapshot::OrderBookSnapshot;
+use crate::spot_log::{LogType, SpotLog};
+use crate::ticker::{RollingStats, Ticker};
 use crate::topic::Topic;
 
+// 撮合进度：最后一条输出、深度增量、逐笔变化的序列号，最后一条已撮合输入的位置，以及是否已暂停撮合
+#[derive(Debug, Clone, Copy)]
+struct MatchProgress {
+    seq_id: u64,
+    depth_seq_id: u64,
+    book_seq_id: u64,
+    partition: i32,
+    offset: i64,
+    halted: bool,
+}
+
+// 由成交增量计算的行情状态，随快照持久化
+#[derive(Debug, Clone, Default)]
+struct MarketState {
+    stats: RollingStats,
+    klines: KlineAggregator,
+}
+
+// 检查点：先刷新生产者，再写入快照，最后保存输入 offset。
+// offset 只在快照落盘后才保存，崩溃重启时从最近一次快照处重放，不会丢失撮合状态
+struct Checkpoint {
+    symbol: String,
+    input_topic: String,
+    snapshot_dir: String,
+    flush_timeout: Duration,
+    consumer: Arc<LoggingConsumer>,
+    producer: FutureProducer,
+}
+
+impl Checkpoint {
+    async fn save(
+        &self,
+        order_book: &Mutex<OrderBook>,
+        progress: MatchProgress,
+        market: &MarketState,
+        client_orders: &ClientOrderWindow,
+    ) -> Result<()> {
+        let producer = self.producer.clone();
+        let flush_timeout = self.flush_timeout;
+        tokio::task::spawn_blocking(move || producer.flush(Timeout::After(flush_timeout))).await??;
+
+        let snapshot = {
+            let order_book = order_book.lock().await;
+            OrderBookSnapshot {
+                depth_seq_id: progress.depth_seq_id,
+                book_seq_id: progress.book_seq_id,
+                halted: progress.halted,
+                stats: market.stats.clone(),
+                klines: market.klines.clone(),
+                client_order_ids: client_orders.entries(),
+                ..OrderBookSnapshot::capture(&self.symbol, &order_book, progress.seq_id, progress.offset)
+            }
+        };
+        snapshot.save(&self.snapshot_dir).await?;
+
+        if progress.offset >= 0 {
+            self.consumer.store_offset(&self.input_topic, progress.partition, progress.offset)?;
+        }
+        Ok(())
+    }
+}
+
+// 引擎角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EngineRole {
+    //主节点：撮合并发布结果
+    Primary,
+    //热备节点：撮合但不发布，接管后转为主节点
+    Standby,
+}
+
+impl fmt::Display for EngineRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let role_str = match self {
+            EngineRole::Primary => "Primary",
+            EngineRole::Standby => "Standby",
+        };
+        write!(f, "{}", role_str)
+    }
+}
+
+// 带序列号的输出流：主节点只发布序列号大于主题中已发布位置的消息，
+// 热备节点只缓存最近的消息，接管时补发主节点尚未发布的部分，保证不丢不重
+struct SequencedStream {
+    topic: String,
+    format: MessageFormat,
+    envelope: bool,           // 消息是否为信封，序列号在 body 中
+    published_seq_id: u64,
+    pending: VecDeque<(u64, Vec<u8>)>,
+}
+
+// 只用于从主题最后一条消息中读取序列号
+#[derive(Deserialize)]
+struct SequencedPayload {
+    seq_id: u64,
+}
+
+impl SequencedStream {
+    fn new(topic: String, format: MessageFormat, envelope: bool) -> Self {
+        SequencedStream { topic, format, envelope, published_seq_id: 0, pending: VecDeque::new() }
+    }
+
+    // 解析主题中一条消息的序列号
+    fn decode_seq_id(&self, payload: &[u8]) -> Result<u64> {
+        if self.envelope {
+            return Ok(Envelope::decode(payload, self.format)?.body.seq_id);
+        }
+        Ok(serde_json::from_slice::<SequencedPayload>(payload)?.seq_id)
+    }
+
+    async fn publish(&mut self, producer: &FutureProducer, key: &str, role: EngineRole, capacity: usize, seq_id: u64, payload: Vec<u8>) {
+        match role {
+            EngineRole::Primary => {
+                if seq_id > self.published_seq_id {
+                    enqueue(producer, &self.topic, key, self.format, &payload).await;
+                    self.published_seq_id = seq_id;
+                }
+            }
+            EngineRole::Standby => {
+                if self.pending.len() >= capacity {
+                    self.pending.pop_front();
+                }
+                self.pending.push_back((seq_id, payload));
+            }
+        }
+    }
+
+    // 读取主题中最后发布的序列号，补发缓存中其后的消息
+    async fn promote(&mut self, brokers: &str, producer: &FutureProducer, key: &str) {
+        self.published_seq_id = last_seq_id(brokers, &self.topic, |payload| self.decode_seq_id(payload)).await;
+
+        if let Some((first_seq_id, _)) = self.pending.front() {
+            if *first_seq_id > self.published_seq_id + 1 {
+                async_error!(format!(
+                    "[ALERT] {} takeover gap on {}: last published seq_id {}, oldest buffered seq_id {}",
+                    key, self.topic, self.published_seq_id, first_seq_id
+                ));
+            }
+        }
+
+        for (seq_id, payload) in std::mem::take(&mut self.pending) {
+            self.publish(producer, key, EngineRole::Primary, 0, seq_id, payload).await;
+        }
+    }
+}
+
+// 主题各分区最后一条消息中序列号的最大值，主题为空或读取失败时返回 0
+async fn last_seq_id(brokers: &str, topic: &str, decode: impl Fn(&[u8]) -> Result<u64>) -> u64 {
+    let brokers = brokers.to_string();
+    let topic_name = topic.to_string();
+    let last_payloads = tokio::task::spawn_blocking(move || {
+        fetch_last_payloads(&brokers, &topic_name, Duration::from_secs(10))
+    }).await;
+
+    match last_payloads {
+        Ok(Ok(payloads)) => {
+            let mut last_seq_id = 0;
+            for payload in payloads {
+                match decode(&payload) {
+                    Ok(seq_id) => last_seq_id = last_seq_id.max(seq_id),
+                    Err(e) => async_error!(format!("Failed to decode last message of {}: {}", topic, e)),
+                }
+            }
+            last_seq_id
+        }
+        Ok(Err(e)) => {
+            async_error!(format!("Failed to fetch last message of {}: {}", topic, e));
+            0
+        }
+        Err(e) => {
+            async_error!(format!("Failed to fetch last message of {}: {}", topic, e));
+            0
+        }
+    }
+}
+
+// 撮合结果及行情发布器，撮合结果和逐笔变化按序列号保证不丢不重，深度行情只由主节点发布
+struct Publisher {
+    brokers: String,
+    key: String,
+    producer: FutureProducer,
+    role: EngineRole,
+    pending_capacity: usize,
+    results: SequencedStream,
+    book_events: Option<SequencedStream>,
+    schema_version: u32,
+    depth_topic: String,
+    depth_format: MessageFormat,
+    ticker_topic: String,
+    kline_topic: String,
+    dead_letter_topic: String,
+    kline_writer: Option<KlineWriter>,
+    persistence: Option<PersistenceSink>,
+}
+
+impl Publisher {
+    // 撮合结果装入信封发布，带回输入消息的关联 ID 和生成时间
+    async fn publish(&mut self, spot_log: SpotLog, correlation_id: Option<&str>, source_timestamp: u64) {
+        let seq_id = spot_log.seq_id;
+        let envelope = Envelope {
+            version: self.schema_version,
+            ..Envelope::new(&self.key, correlation_id.map(str::to_string), Some(source_timestamp), spot_log)
+        };
+        match envelope.encode(self.results.format) {
+            Ok(payload) => {
+                self.results.publish(&self.producer, &self.key, self.role, self.pending_capacity, seq_id, payload).await;
+            }
+            Err(e) => async_error!(format!("Failed to serialize match result: {}", e)),
+        }
+    }
+
+    async fn publish_book_event(&mut self, event: &OrderBookEvent) {
+        let Some(book_events) = self.book_events.as_mut() else {
+            return;
+        };
+        match serde_json::to_vec(event) {
+            Ok(payload) => {
+                book_events.publish(&self.producer, &self.key, self.role, self.pending_capacity, event.seq_id, payload).await;
+            }
+            Err(e) => async_error!(format!("Failed to serialize book event: {}", e)),
+        }
+    }
+
+    // 深度行情只由主节点发布，热备节点接管后先发布一次全量快照供下游重新同步
+    async fn publish_depth(&self, update: &DepthUpdate) {
+        if self.role != EngineRole::Primary {
+            return;
+        }
+        match update.encode(self.depth_format) {
+            Ok(payload) => enqueue(&self.producer, &self.depth_topic, &self.key, self.depth_format, &payload).await,
+            Err(e) => async_error!(format!("Failed to serialize depth update: {}", e)),
+        }
+    }
+
+    async fn publish_ticker(&self, ticker: &Ticker) {
+        if self.role != EngineRole::Primary {
+            return;
+        }
+        match serde_json::to_vec(ticker) {
+            Ok(payload) => enqueue(&self.producer, &self.ticker_topic, &self.key, MessageFormat::Json, &payload).await,
+            Err(e) => async_error!(format!("Failed to serialize ticker: {}", e)),
+        }
+    }
+
+    // 已收盘的 K 线同时写入数据库，重复写入会覆盖，因此无需按序列号去重
+    async fn publish_kline(&self, kline: Kline) {
+        if self.role != EngineRole::Primary {
+            return;
+        }
+        match serde_json::to_vec(&kline) {
+            Ok(payload) => enqueue(&self.producer, &self.kline_topic, &self.key, MessageFormat::Json, &payload).await,
+            Err(e) => async_error!(format!("Failed to serialize kline: {}", e)),
+        }
+        if let (true, Some(kline_writer)) = (kline.closed, &self.kline_writer) {
+            kline_writer.write(kline).await;
+        }
+    }
+
+    // 死信只由主节点发布，原始字节和消息头原样保留
+    async fn publish_dead_letter(&self, dead_letter: DeadLetter) {
+        async_error!(format!(
+            "Dead letter {} at {}:{} of {}: {}",
+            dead_letter.reason, dead_letter.partition, dead_letter.offset, self.key, dead_letter.error
+        ));
+        metrics::symbol(&self.key).dead_letter(dead_letter.reason);
+        if self.role != EngineRole::Primary {
+            return;
+        }
+        enqueue_with_headers(&self.producer, &self.dead_letter_topic, &self.key, dead_letter.headers(), &dead_letter.payload).await;
+    }
+
+    // 成交和订单状态写入幂等，主备节点都写入，接管时不会遗漏热备期间的成交。
+    // 未开启持久化时 `events` 不会被执行
+    async fn persist(&self, events: impl FnOnce() -> Vec<PersistEvent>) {
+        if let Some(persistence) = &self.persistence {
+            persistence.write(events()).await;
+        }
+    }
+
+    // 转为主节点：各输出流补发主节点尚未发布的部分
+    async fn promote(&mut self) {
+        self.role = EngineRole::Primary;
+        self.results.promote(&self.brokers, &self.producer, &self.key).await;
+        if let Some(book_events) = self.book_events.as_mut() {
+            book_events.promote(&self.brokers, &self.producer, &self.key).await;
+        }
+
+        async_info!(format!("{} promoted to primary after seq_id {}", self.key, self.results.published_seq_id));
+    }
+
+    // 转为热备节点：租约丢失后立即停止发布，排空期间的结果只缓存不发布，避免与接管的节点重复发布
+    async fn demote(&mut self) {
+        self.role = EngineRole::Standby;
+        async_info!(format!("{} demoted to standby after seq_id {}", self.key, self.results.published_seq_id));
+    }
+}
+
+// 输入消息的原始内容和位置，无法撮合时转入死信主题
+struct MessageSource {
+    headers: Option<OwnedHeaders>,
+    payload: Vec<u8>,
+    partition: i32,
+    offset: i64,
+    received_at: Instant,
+}
+
+impl MessageSource {
+    fn dead_letter(&self, source_topic: &str, reason: DeadLetterReason, error: String) -> DeadLetter {
+        DeadLetter {
+            reason,
+            error,
+            source_topic: source_topic.to_string(),
+            partition: self.partition,
+            offset: self.offset,
+            headers: self.headers.clone(),
+            payload: self.payload.clone(),
+        }
+    }
+}
+
+// 消费到的一条输入消息，解码失败时附带死信原因
+struct Incoming {
+    decoded: Result<Envelope, (DeadLetterReason, String)>,
+    source: MessageSource,
+}
+
+// 一条输入消息对订单簿的全部影响
+struct Matched {
+    timestamp: u64,
+    incoming: Option<Order>,  // 新订单或撤单，管理操作时为 None
+    results: Vec<SpotLog>,
+    depth_changes: Depth,
+    book_events: Vec<BookEvent>,
+    best_bid: Option<f64>,
+    best_ask: Option<f64>,
+    levels: (usize, usize),
+    orders: usize,
+}
+
+impl Matched {
+    // 取出订单簿处理这条消息产生的深度、逐笔变化和执行回报，
+    // 执行回报追加在撮合结果之后，时间统一为输入消息的时间，主备节点和重放时结果一致
+    fn collect(order_book: &mut OrderBook, timestamp: u64, incoming: Option<Order>, mut results: Vec<SpotLog>) -> Self {
+        results.extend(order_book.take_execution_reports()
+            .into_iter()
+            .map(|report| ExecutionReport { timestamp, ..report }.into_spot_log()));
+        Matched {
+            timestamp,
+            incoming,
+            results,
+            depth_changes: order_book.take_depth_changes(),
+            book_events: order_book.take_book_events(),
+            best_bid: order_book.best_bid(),
+            best_ask: order_book.best_ask(),
+            levels: order_book.level_count(),
+            orders: order_book.len(),
+        }
+    }
+}
+
+// 消息处理器的各定时任务间隔
+struct ProcessorIntervals {
+    snapshot: Duration,
+    depth_snapshot: Duration,
+    ticker: Duration,
+    kline: Duration,
+}
+
+// 待撮合消息 channel 的容量
+const CHANNEL_CAPACITY: usize = 100_000;
+// 消费延迟的刷新间隔
+const CONSUMER_LAG_INTERVAL: Duration = Duration::from_secs(5);
+// 从快照恢复后定位输入主题的超时时间
+const SEEK_TIMEOUT: Duration = Duration::from_secs(5);
+
 #[allow(dead_code)]
 pub struct Engine {
     symbol: String,
     base_coin: String,
     quote_coin: String,
+    rules: Arc<SymbolRules>,
     order_book: Arc<Mutex<OrderBook>>,
-    // 增加缓冲区大小，减少背压
-    spot_log_sender: mpsc::Sender<SpotLog>,
-    spot_log_receiver: Option<mpsc::Receiver<SpotLog>>,
+    // 增加缓冲区大小，减少背压。消息附带原始内容、分区、offset 和消费时间
+    spot_log_sender: Option<mpsc::Sender<Incoming>>,
+    spot_log_receiver: Option<mpsc::Receiver<Incoming>>,
+    progress: MatchProgress,
+    market: MarketState,
+    // 最近受理的客户端订单号，随快照持久化
+    client_orders: ClientOrderWindow,
+    // 从 exchange_order 恢复时表中已有的订单号，重放输入主题时跳过
+    known_orders: HashSet<u64>,
+    role: watch::Receiver<EngineRole>,
+    market_data: MarketDataStore,
+    metrics: Arc<SymbolMetrics>,
+    health: Arc<EngineHealth>,
+    spot_log_format: MessageFormat,
 }
 
 impl Engine {
-    pub fn new(symbol: String, base_coin: String, quote_coin: String) -> Self {
+    pub fn new(symbol: String, base_coin: String, quote_coin: String, rules: SymbolRules) -> Self {
         // 增大channel容量，避免消息堆积导致的背压
-        let (spot_log_sender, spot_log_receiver) = mpsc::channel(100_000);
+        let (spot_log_sender, spot_log_receiver) = mpsc::channel(CHANNEL_CAPACITY);
+        let metrics = metrics::symbol(&symbol);
+        metrics.queue_capacity.store(CHANNEL_CAPACITY as u64, Ordering::Relaxed);
 
         Engine {
             symbol,
             base_coin,
             quote_coin,
+            rules: Arc::new(rules),
             order_book: Arc::new(Mutex::new(OrderBook::new())),
-            spot_log_sender,
+            spot_log_sender: Some(spot_log_sender),
             spot_log_receiver: Some(spot_log_receiver),
+            progress: MatchProgress { seq_id: 0, depth_seq_id: 0, book_seq_id: 0, partition: 0, offset: -1, halted: false },
+            market: MarketState::default(),
+            client_orders: ClientOrderWindow::default(),
+            known_orders: HashSet::new(),
+            role: watch::channel(EngineRole::Primary).1,
+            market_data: MarketDataStore::new(),
+            metrics,
+            health: Arc::new(EngineHealth::default()),
+            spot_log_format: MessageFormat::default(),
         }
     }
 
-    pub async fn run(&mut self) -> Result<()> {
-        // 启动消息处理器
-        self.start_message_processor().await;
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
 
-        // 启动消费者
-        self.start_consumer().await;
+    /// 设置引擎角色，以热备角色启动的引擎在角色变为主节点时接管发布
+    pub fn set_role(&mut self, role: watch::Receiver<EngineRole>) {
+        self.role = role;
+    }
 
-        Ok(())
+    /// 设置行情存储，引擎运行期间持续写入最新行情
+    pub fn set_market_data(&mut self, market_data: MarketDataStore) {
+        self.market_data = market_data;
     }
 
-    // 将消费者逻辑拆分出来
-    async fn start_consumer(&mut self) {
-        // 这里从全局配置中获取 brokers 等数据，但立即克隆出来，以避免持有 MutexGuard
-        let brokers = {
-            let config = Config::global();
-            config.kafka_config.brokers.clone()
+    /// 设置健康状态，引擎运行期间更新启动阶段和撮合循环心跳
+    pub fn set_health(&mut self, health: Arc<EngineHealth>) {
+        self.health = health;
+    }
+
+    /// 设置输入订单和撮合结果的编码格式，输入消息带有 format 消息头时以消息头为准
+    pub fn set_spot_log_format(&mut self, format: MessageFormat) {
+        self.spot_log_format = format;
+    }
+
+    /// 运行撮合引擎，直到 `shutdown` 收到关闭信号后依次：
+    /// 停止消费、撮合完已接收的消息、刷新生产者、写入最终快照并提交 offset。
+    /// 撮合任务异常退出时返回错误，由监管器从最近一次快照重启
+    pub async fn run(&mut self, mut shutdown: watch::Receiver<bool>) -> Result<()> {
+        // 这里从全局配置中获取数据，但立即克隆出来，以避免持有 MutexGuard
+        let config = Config::global();
+        let brokers = config.kafka_config.brokers.clone();
+        let shutdown_timeout = Duration::from_millis(config.engine_config.shutdown_timeout_ms);
+        let snapshot_dir = config.engine_config.snapshot_dir.clone();
+        let snapshot_interval = Duration::from_millis(config.engine_config.snapshot_interval_ms);
+        let depth_snapshot_interval = Duration::from_millis(config.market_data_config.depth_snapshot_interval_ms);
+        let ticker_interval = Duration::from_millis(config.market_data_config.ticker_interval_ms);
+        let kline_interval = Duration::from_millis(config.market_data_config.kline_interval_ms);
+        let standby_enabled = config.node_config.standby_enabled;
+
+        let bootstrap_from_db = config.engine_config.bootstrap_from_db;
+        let schema_version = config.engine_config.output_schema_version;
+        if !(MIN_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&schema_version) {
+            return Err(anyhow!(
+                "Unsupported output schema version {}, expected {} to {}", schema_version, MIN_SCHEMA_VERSION, SCHEMA_VERSION
+            ));
+        }
+
+        let restored = match self.restore_snapshot(&snapshot_dir).await {
+            Ok(restored) => restored,
+            Err(e) if bootstrap_from_db => {
+                async_error!(format!("Failed to restore snapshot of {}, falling back to exchange_order: {}", self.symbol, e));
+                false
+            }
+            Err(e) => return Err(e),
         };
+        if !restored && bootstrap_from_db {
+            let (order_book, known_orders) = load_order_book(&self.symbol).await?;
+            self.order_book = Arc::new(Mutex::new(order_book));
+            self.known_orders = known_orders;
+            self.seed_sequences(&brokers).await;
+        }
+        self.client_orders.set_capacity(config.engine_config.client_order_window);
 
-        let group_id = format!("{}_{}_group", self.base_coin, self.quote_coin);
+        // 主备节点各自消费完整的输入流，offset 与本机快照对应，因此使用按节点区分的消费组
+        let group_id = match node_id() {
+            Some(node_id) if standby_enabled => format!("{}_{}_{}_group", self.base_coin, self.quote_coin, node_id),
+            _ => format!("{}_{}_group", self.base_coin, self.quote_coin),
+        };
         let topic = format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotNewOrder);
 
         async_info!("group_id   ", group_id.clone());
         async_info!("group_id   ", topic.clone());
 
+        let consumer = create_consumer(brokers.as_str(), &group_id, &topic)
+            .map(Arc::new)
+            .map_err(|e| anyhow!("Failed to create consumer: {}", e))?;
+        let replay_target = self.replay_target(&consumer, &topic, shutdown_timeout).await;
+        let producer = create_producer(brokers.as_str())
+            .ok_or_else(|| anyhow!("Failed to create producer"))?;
+
+        let publisher = Publisher {
+            brokers,
+            key: self.symbol.clone(),
+            producer: producer.clone(),
+            role: EngineRole::Standby,
+            pending_capacity: config.node_config.standby_buffer_size,
+            results: SequencedStream::new(
+                format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotMatchResult), self.spot_log_format, true,
+            ),
+            book_events: config.market_data_config.book_event_enabled.then(|| {
+                SequencedStream::new(
+                    format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotBookEvent), MessageFormat::Json, false,
+                )
+            }),
+            schema_version,
+            depth_topic: format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotDepthUpdate),
+            depth_format: config.market_data_config.depth_format,
+            ticker_topic: format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotTicker),
+            kline_topic: format!("{}_{}_{}", self.base_coin, self.quote_coin, Topic::SpotKline),
+            dead_letter_topic: dead_letter::topic(&self.symbol),
+            kline_writer: config.market_data_config.kline_persist_enabled.then(|| KlineWriter::spawn(10_000)),
+            persistence: config.persistence_config.enabled.then(|| PersistenceSink::spawn(
+                &self.symbol,
+                &snapshot_dir,
+                config.persistence_config.queue_size,
+                config.persistence_config.batch_size,
+                Duration::from_millis(config.persistence_config.flush_interval_ms),
+            )),
+        };
+        let checkpoint = Arc::new(Checkpoint {
+            symbol: self.symbol.clone(),
+            input_topic: topic,
+            snapshot_dir,
+            flush_timeout: shutdown_timeout,
+            consumer: consumer.clone(),
+            producer,
+        });
+
+        // 启动消息处理器
+        let mut processor = self.start_message_processor(
+            checkpoint.clone(),
+            publisher,
+            ProcessorIntervals {
+                snapshot: snapshot_interval,
+                depth_snapshot: depth_snapshot_interval,
+                ticker: ticker_interval,
+                kline: kline_interval,
+            },
+            replay_target,
+        );
+
+        // 启动消费者
+        self.start_consumer(&consumer, &mut shutdown, &mut processor).await?;
+        async_info!("Consumer stopped, draining engine: ", self.symbol.clone());
+
+        match tokio::time::timeout(shutdown_timeout, self.drain(&checkpoint, processor)).await {
+            Ok(result) => result,
+            Err(_) => Err(anyhow!("Engine {} shutdown timed out after {:?}", self.symbol, shutdown_timeout)),
+        }
+    }
+
+    // 订单簿从数据库恢复时没有快照中的序列号，从各输出主题已发布的位置继续编号，
+    // 下游不会因序列号回退而把新消息当作重复丢弃
+    async fn seed_sequences(&mut self, brokers: &str) {
+        let topic = |kind: Topic| format!("{}_{}_{}", self.base_coin, self.quote_coin, kind);
+        let spot_log_format = self.spot_log_format;
+        let depth_format = Config::global().market_data_config.depth_format;
 
+        self.progress.seq_id = last_seq_id(brokers, &topic(Topic::SpotMatchResult), |payload| {
+            Ok(Envelope::decode(payload, spot_log_format)?.body.seq_id)
+        }).await;
+        self.progress.book_seq_id = last_seq_id(brokers, &topic(Topic::SpotBookEvent), |payload| {
+            Ok(serde_json::from_slice::<SequencedPayload>(payload)?.seq_id)
+        }).await;
+        self.progress.depth_seq_id = last_seq_id(brokers, &topic(Topic::SpotDepthUpdate), |payload| match depth_format {
+            MessageFormat::Json => Ok(serde_json::from_slice::<SequencedPayload>(payload)?.seq_id),
+            MessageFormat::Flatbuffers => Ok(DepthUpdate::parse_depth_flatbuffer(payload).map_err(|e| anyhow!(e))?.seq_id),
+        }).await;
+
+        async_info!(format!(
+            "Seeded {} from published output: seq_id {}, book_seq_id {}, depth_seq_id {}",
+            self.symbol, self.progress.seq_id, self.progress.book_seq_id, self.progress.depth_seq_id
+        ));
+    }
 
-        let consumer = match create_consumer(brokers.as_str(), &group_id, &topic) {
-            Ok(consumer) => consumer,
+    // 启动时输入主题 0 号分区最后一条消息的 offset，撮合到这里即视为追上重放；读取失败时不等待重放
+    async fn replay_target(&self, consumer: &Arc<LoggingConsumer>, topic: &str, timeout: Duration) -> i64 {
+        let consumer = consumer.clone();
+        let topic_name = topic.to_string();
+        let watermarks = tokio::task::spawn_blocking(move || {
+            let partitions = fetch_partitions(consumer.as_ref(), &topic_name, timeout)?;
+            consumer.fetch_watermarks(&topic_name, 0, timeout).map(|(_, high)| (partitions.len(), high))
+        }).await;
+        match watermarks {
+            Ok(Ok((partitions, high))) => {
+                // 撮合进度只记录一个偏移量，输入主题必须只有一个分区，否则无法保证撮合顺序
+                if partitions > 1 {
+                    async_error!(format!(
+                        "[ALERT] Input topic {} has {} partitions, only partition 0 is tracked for replay",
+                        topic, partitions
+                    ));
+                }
+                high - 1
+            }
+            Ok(Err(e)) => {
+                async_error!(format!("Failed to fetch watermarks of {}: {}", topic, e));
+                -1
+            }
             Err(e) => {
-                async_error!("Failed to create consumer: {}", e);
-                return;
+                async_error!(format!("Failed to fetch watermarks of {}: {}", topic, e));
+                -1
             }
-        };
+        }
+    }
+
+    // 启动时从快照恢复订单簿，没有快照时返回 false
+    async fn restore_snapshot(&mut self, snapshot_dir: &str) -> Result<bool> {
+        if let Some(mut snapshot) = OrderBookSnapshot::load(snapshot_dir, &self.symbol).await? {
+            async_info!(format!(
+                "Restore snapshot for {}: seq_id {}, offset {}",
+                self.symbol, snapshot.seq_id, snapshot.offset
+            ));
+            self.progress.seq_id = snapshot.seq_id;
+            self.progress.depth_seq_id = snapshot.depth_seq_id;
+            self.progress.book_seq_id = snapshot.book_seq_id;
+            self.progress.offset = snapshot.offset;
+            self.progress.halted = snapshot.halted;
+            self.market.stats = std::mem::take(&mut snapshot.stats);
+            self.market.klines = std::mem::take(&mut snapshot.klines);
+            self.client_orders = ClientOrderWindow::restore(std::mem::take(&mut snapshot.client_order_ids));
+            self.order_book = Arc::new(Mutex::new(snapshot.into_order_book()));
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    // 将消费者逻辑拆分出来，收到关闭信号后返回；消息处理器提前退出说明撮合异常，返回错误
+    async fn start_consumer(
+        &mut self,
+        consumer: &Arc<LoggingConsumer>,
+        shutdown: &mut watch::Receiver<bool>,
+        processor: &mut JoinHandle<(MatchProgress, MarketState, ClientOrderWindow)>,
+    ) -> Result<()> {
+        let mut lag_timer = tokio::time::interval(CONSUMER_LAG_INTERVAL);
+        // 最后一条已消费消息的主题、分区和 offset，用于计算消费延迟
+        let mut last_consumed: Option<(String, i32, i64)> = None;
+        // 快照写入后才保存 offset，自动提交又有间隔，已提交的 offset 可能落后于快照。
+        // 收到快照中已包含的消息时定位到快照之后，只定位一次
+        let resume_offset = self.progress.offset;
+        let mut sought = resume_offset < 0;
 
         // 消费消息的主循环
         loop {
-            match consumer.recv().await {
-                Ok(m) => {
-                    if let Err(e) = self.process_kafka_message(&consumer, &m).await {
-                        async_error!("Error processing message: {}", e);
+            if *shutdown.borrow() {
+                return Ok(());
+            }
+
+            tokio::select! {
+                // 发送端被丢弃同样视为关闭信号
+                changed = shutdown.changed() => {
+                    if changed.is_err() {
+                        return Ok(());
+                    }
+                }
+                result = &mut *processor => {
+                    return match result {
+                        Ok(_) => Err(anyhow!("Message processor of {} exited unexpectedly", self.symbol)),
+                        Err(e) => Err(anyhow!("Message processor of {} failed: {}", self.symbol, e)),
+                    };
+                }
+                _ = lag_timer.tick() => {
+                    // 查询高水位需要请求 broker，放在阻塞线程中进行，不等待结果
+                    if let Some((topic, partition, offset)) = last_consumed.clone() {
+                        let consumer = consumer.clone();
+                        let metrics = self.metrics.clone();
+                        tokio::task::spawn_blocking(move || {
+                            if let Ok((_, high)) = consumer.fetch_watermarks(&topic, partition, CONSUMER_LAG_INTERVAL) {
+                                metrics.consumer_lag.store(high - offset - 1, Ordering::Relaxed);
+                            }
+                        });
+                    }
+                }
+                message = consumer.recv() => match message {
+                    Ok(m) if m.offset() <= resume_offset => {
+                        if !sought {
+                            sought = true;
+                            async_info!(format!(
+                                "Seek {} from offset {} to {} after snapshot", m.topic(), m.offset(), resume_offset + 1
+                            ));
+                            if let Err(e) = consumer.seek(m.topic(), m.partition(), Offset::Offset(resume_offset + 1), SEEK_TIMEOUT) {
+                                async_error!(format!("Failed to seek {}: {}", m.topic(), e));
+                            }
+                        }
+                    }
+                    Ok(m) => {
+                        last_consumed = Some((m.topic().to_string(), m.partition(), m.offset()));
+                        if let Err(e) = self.process_kafka_message(&m).await {
+                            async_error!(format!("Error processing message: {}", e));
+                        }
+                    },
+                    Err(e) => {
+                        async_error!(format!("Kafka receive error: {}", e));
+                        tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                     }
-                },
-                Err(e) => {
-                    async_error!("Kafka receive error: {}", e);
-                    tokio::time::sleep(tokio::time::Duration::from_secs(1)).await;
                 }
             }
         }
     }
 
-    // 消息处理器
-    async fn start_message_processor(&mut self) {
+    // 排空引擎：撮合完已接收的消息，写入最终检查点并同步提交 offset
+    async fn drain(&mut self, checkpoint: &Checkpoint, processor: JoinHandle<(MatchProgress, MarketState, ClientOrderWindow)>) -> Result<()> {
+        // 关闭channel，处理器处理完剩余消息后退出
+        self.spot_log_sender.take();
+        (self.progress, self.market, self.client_orders) = processor.await?;
+
+        checkpoint.save(&self.order_book, self.progress, &self.market, &self.client_orders).await?;
+        if self.progress.offset >= 0 {
+            checkpoint.consumer.commit_consumer_state(CommitMode::Sync)?;
+        }
+
+        async_info!(format!(
+            "Engine {} stopped: seq_id {}, offset {}",
+            self.symbol, self.progress.seq_id, self.progress.offset
+        ));
+        Ok(())
+    }
+
+    // 消息处理器，定期写入检查点、发布全量深度、行情和 K 线，角色变为主节点时接管发布，
+    // 撮合到 `replay_target` 后标记为已追上重放，channel关闭后返回最终的撮合进度和行情状态
+    fn start_message_processor(
+        &mut self,
+        checkpoint: Arc<Checkpoint>,
+        mut publisher: Publisher,
+        intervals: ProcessorIntervals,
+        replay_target: i64,
+    ) -> JoinHandle<(MatchProgress, MarketState, ClientOrderWindow)> {
         let spot_log_receiver = self.spot_log_receiver.take()
             .expect("Receiver should exist");
         let order_book = self.order_book.clone();
+        let rules = self.rules.clone();
+        let mut role = self.role.clone();
+        let mut progress = self.progress;
+        let mut market = std::mem::take(&mut self.market);
+        let mut client_orders = std::mem::take(&mut self.client_orders);
+        let known_orders = std::mem::take(&mut self.known_orders);
+        let duplicate_policy = Config::global().engine_config.duplicate_policy;
+        let symbol_market = self.market_data.symbol(&self.symbol);
+        let metrics = self.metrics.clone();
+        let health = self.health.clone();
+        let recent_trades_size = Config::global().market_data_config.recent_trades_size;
 
         tokio::spawn(async move {
+            {
+                let order_book_guard = order_book.lock().await;
+                let mut view = symbol_market.write().expect("market data poisoned");
+                view.reset(&order_book_guard, recent_trades_size, progress.depth_seq_id);
+                view.set_halted(progress.halted);
+            }
+
+            let mut replaying = progress.offset < replay_target;
+            health.set_phase(if replaying { EnginePhase::Replaying } else { EnginePhase::Consuming });
+
             // let mut batch = Vec::with_capacity(100); // 批量处理缓冲区
             let mut receiver = spot_log_receiver;
+            let mut snapshot_timer = tokio::time::interval(intervals.snapshot);
+            let mut depth_snapshot_timer = tokio::time::interval(intervals.depth_snapshot);
+            let mut ticker_timer = tokio::time::interval(intervals.ticker);
+            let mut kline_timer = tokio::time::interval(intervals.kline);
+            let mut snapshot_offset = progress.offset;
+            let mut role_open = true;
+
+            if *role.borrow_and_update() == EngineRole::Primary {
+                publisher.promote().await;
+            }
 
-            while let Some(spot_log) = receiver.recv().await {
-                if let Some(order) = spot_log.order {
-                    let mut order_book_guard = order_book.lock().await;
-                    order_book_guard.add_order(order);
+            loop {
+                health.beat();
+                // 降级不等待 select 轮到角色分支，下一条消息处理前即停止发布
+                if publisher.role == EngineRole::Primary && *role.borrow() == EngineRole::Standby {
+                    publisher.demote().await;
+                }
+                tokio::select! {
+                    changed = role.changed(), if role_open => {
+                        if changed.is_err() {
+                            role_open = false;
+                        } else if *role.borrow_and_update() == EngineRole::Primary && publisher.role == EngineRole::Standby {
+                            publisher.promote().await;
+                            depth_snapshot_timer.reset_immediately();
+                        }
+                    }
+                    received = receiver.recv() => {
+                        let Some(Incoming { decoded, source }) = received else {
+                            break;
+                        };
+                        let (partition, offset, received_at) = (source.partition, source.offset, source.received_at);
+                        metrics.queue_length.store(receiver.len() as u64, Ordering::Relaxed);
+                        // 快照中已包含的消息不再撮合，否则会重复成交并重复分配序列号
+                        if offset <= progress.offset {
+                            continue;
+                        }
+
+                        let (correlation_id, source_timestamp, spot_log) = match decoded {
+                            Ok(Envelope { correlation_id, timestamp, body, .. }) => (correlation_id, timestamp, Some(body)),
+                            Err((reason, error)) => {
+                                publisher.publish_dead_letter(source.dead_letter(&checkpoint.input_topic, reason, error)).await;
+                                (None, 0, None)
+                            }
+                        };
+
+                        let mut force_snapshot = false;
+                        let matched = match spot_log.map(|spot_log| (spot_log.log_type, spot_log.admin, spot_log.order)) {
+                            None => None,
+                            Some((LogType::Admin, Some(admin), _)) => {
+                                async_info!(format!("Admin {} on {} by {}", admin.command, checkpoint.symbol, admin.operator));
+                                let mut order_book_guard = order_book.lock().await;
+                                let cancelled = match &admin.command {
+                                    AdminCommand::Halt => {
+                                        progress.halted = true;
+                                        Vec::new()
+                                    }
+                                    AdminCommand::Resume => {
+                                        progress.halted = false;
+                                        Vec::new()
+                                    }
+                                    AdminCommand::CancelAll { user_id } => order_book_guard.cancel_all(*user_id),
+                                    AdminCommand::Snapshot => {
+                                        force_snapshot = true;
+                                        Vec::new()
+                                    }
+                                    AdminCommand::SetLogLevel { level } => {
+                                        set_log_level(*level);
+                                        Vec::new()
+                                    }
+                                };
+                                // 管理操作本身先于其产生的撤单发布，留档
+                                let timestamp = admin.timestamp;
+                                let results = std::iter::once(admin.into_spot_log()).chain(cancelled).collect();
+                                Some(Matched::collect(&mut order_book_guard, timestamp, None, results))
+                            }
+                            Some((log_type, _, Some(order))) => {
+                                match log_type {
+                                    LogType::NewOrder => metrics.new_orders_in.fetch_add(1, Ordering::Relaxed),
+                                    _ => metrics.cancel_orders_in.fetch_add(1, Ordering::Relaxed),
+                                };
+                                let resting = order_book.lock().await.contains(order.id);
+                                // 旧撮合已处理过的订单：新订单已在表中，撤单的订单已不在订单簿中
+                                let replayed = known_orders.contains(&order.id) && match log_type {
+                                    LogType::NewOrder => true,
+                                    LogType::CancelOrder => !resting,
+                                    _ => false,
+                                };
+                                let accepted = match log_type {
+                                    LogType::NewOrder if progress.halted => Err(RejectReason::Halted),
+                                    LogType::NewOrder if resting => Err(RejectReason::DuplicateOrderId),
+                                    LogType::NewOrder if client_orders.contains(&order) => Err(RejectReason::DuplicateClientOrderId),
+                                    LogType::NewOrder => rules.validate(&order),
+                                    LogType::CancelOrder if !resting => Err(RejectReason::UnknownOrder),
+                                    LogType::CancelOrder => Ok(()),
+                                    LogType::Trade | LogType::Admin | LogType::ExecutionReport => Err(RejectReason::UnexpectedLogType),
+                                };
+
+                                let timestamp = order.timestamp;
+                                if replayed {
+                                    async_info!(format!("Skip replayed {} {} of {}: already in exchange_order", log_type, order.id, checkpoint.symbol));
+                                    None
+                                } else if accepted == Err(RejectReason::DuplicateClientOrderId) && duplicate_policy == DuplicatePolicy::Ignore {
+                                    async_info!(format!(
+                                        "Ignore duplicate order {} of {}: user {}, client order id {:?}",
+                                        order.id, checkpoint.symbol, order.user_id, order.client_order_id
+                                    ));
+                                    metrics.reject(&RejectReason::DuplicateClientOrderId);
+                                    None
+                                } else if let Err(reason) = accepted {
+                                    async_error!(format!("Reject {} {} of {}: {}", log_type, order.id, checkpoint.symbol, reason));
+                                    metrics.reject(&reason);
+                                    // 撤单时订单恰好成交或已撤销、重复投递的订单属于正常情况，只回报拒绝，不进入死信主题
+                                    let kind = match reason {
+                                        RejectReason::UnknownOrder | RejectReason::DuplicateClientOrderId => None,
+                                        RejectReason::UnexpectedLogType => Some(DeadLetterReason::UnknownType),
+                                        _ => Some(DeadLetterReason::ValidationFailed),
+                                    };
+                                    if let Some(kind) = kind {
+                                        publisher.publish_dead_letter(source.dead_letter(&checkpoint.input_topic, kind, reason.to_string())).await;
+                                    }
+                                    let rejected = ExecutionReport::rejected(&order, reason.to_string()).into_spot_log();
+                                    Some(Matched::collect(&mut *order_book.lock().await, timestamp, None, vec![rejected]))
+                                } else {
+                                    let incoming = order.clone();
+                                    let mut order_book_guard = order_book.lock().await;
+                                    let results = match log_type {
+                                        LogType::CancelOrder => order_book_guard.cancel_order(order.id, order.quantity),
+                                        _ => {
+                                            // 新订单中的已成交数量被忽略，从 0 开始累计
+                                            let order = Order { filled_quantity: 0.0, ..order };
+                                            client_orders.insert(&order);
+                                            let accepted = ExecutionReport::new(&order, ExecutionStatus::Accepted, order.quantity);
+                                            std::iter::once(accepted.into_spot_log()).chain(order_book_guard.add_order(order)).collect()
+                                        }
+                                    };
+                                    Some(Matched::collect(&mut order_book_guard, timestamp, Some(incoming), results))
+                                }
+                            }
+                            Some((log_type, _, None)) => {
+                                let error = format!("{} message without payload", log_type);
+                                publisher.publish_dead_letter(source.dead_letter(&checkpoint.input_topic, DeadLetterReason::UnknownType, error)).await;
+                                None
+                            }
+                        };
+
+                        if let Some(Matched { timestamp, incoming, results, depth_changes, book_events, best_bid, best_ask, levels, orders }) = matched {
+                            metrics.set_book(levels, orders);
+                            // K 线按输入消息的时间收盘，重放时结果与原先相同
+                            for kline in market.klines.advance(timestamp) {
+                                publisher.publish_kline(kline).await;
+                            }
+                            for trade in results.iter().filter_map(|result| result.trade.as_ref()) {
+                                metrics.trades_out.fetch_add(1, Ordering::Relaxed);
+                                market.stats.add_trade(trade.price, trade.quantity, trade.timestamp);
+                                for kline in market.klines.add_trade(&checkpoint.symbol, trade.price, trade.quantity, trade.timestamp) {
+                                    publisher.publish_kline(kline).await;
+                                }
+                            }
+
+                            let depth_changed = !depth_changes.bids.is_empty() || !depth_changes.asks.is_empty();
+                            if depth_changed {
+                                progress.depth_seq_id += 1;
+                            }
+
+                            {
+                                let mut view = symbol_market.write().expect("market data poisoned");
+                                if depth_changed {
+                                    view.apply_depth(&depth_changes, progress.depth_seq_id);
+                                }
+                                for event in &book_events {
+                                    view.apply_book_event(event, incoming.as_ref());
+                                }
+                                for trade in results.iter().filter_map(|result| result.trade.as_ref()) {
+                                    view.add_trade(trade.clone());
+                                }
+                                if depth_changed {
+                                    view.update_ticker(market.stats.ticker(&checkpoint.symbol, best_bid, best_ask, timestamp));
+                                }
+                                view.set_halted(progress.halted);
+                            }
+
+                            let mut results = results;
+                            for result in &mut results {
+                                progress.seq_id += 1;
+                                result.seq_id = progress.seq_id;
+                            }
+                            publisher.persist(|| PersistEvent::collect(timestamp, incoming.as_ref(), &results, &book_events)).await;
+                            for result in results {
+                                publisher.publish(result, correlation_id.as_deref(), source_timestamp).await;
+                            }
+
+                            for event in book_events {
+                                progress.book_seq_id += 1;
+                                let event = OrderBookEvent::new(&checkpoint.symbol, progress.book_seq_id, timestamp, event);
+                                publisher.publish_book_event(&event).await;
+                            }
+
+                            if depth_changed {
+                                let update = DepthUpdate::new(
+                                    DepthKind::Update, &checkpoint.symbol, progress.depth_seq_id, timestamp, depth_changes,
+                                );
+                                publisher.publish_depth(&update).await;
+                            }
+                            metrics.match_latency.observe(received_at.elapsed());
+                        }
+                        progress.partition = partition;
+                        progress.offset = offset;
+                        if replaying && progress.offset >= replay_target {
+                            replaying = false;
+                            health.set_phase(EnginePhase::Consuming);
+                            async_info!(format!("Engine {} caught up at offset {}", checkpoint.symbol, progress.offset));
+                        }
+
+                        if force_snapshot {
+                            match checkpoint.save(&order_book, progress, &market, &client_orders).await {
+                                Ok(()) => snapshot_offset = progress.offset,
+                                Err(e) => async_error!(format!("Failed to checkpoint {}: {}", checkpoint.symbol, e)),
+                            }
+                        }
+                    }
+                    _ = snapshot_timer.tick() => {
+                        if progress.offset > snapshot_offset {
+                            match checkpoint.save(&order_book, progress, &market, &client_orders).await {
+                                Ok(()) => snapshot_offset = progress.offset,
+                                Err(e) => async_error!(format!("Failed to checkpoint {}: {}", checkpoint.symbol, e)),
+                            }
+                        }
+                    }
+                    _ = depth_snapshot_timer.tick() => {
+                        let depth = order_book.lock().await.depth(usize::MAX);
+                        let update = DepthUpdate::new(
+                            DepthKind::Snapshot, &checkpoint.symbol, progress.depth_seq_id, current_timestamp(), depth,
+                        );
+                        publisher.publish_depth(&update).await;
+                    }
+                    _ = ticker_timer.tick() => {
+                        let (best_bid, best_ask) = {
+                            let order_book_guard = order_book.lock().await;
+                            (order_book_guard.best_bid(), order_book_guard.best_ask())
+                        };
+                        let ticker = market.stats.ticker(&checkpoint.symbol, best_bid, best_ask, current_timestamp());
+                        symbol_market.write().expect("market data poisoned").update_ticker(ticker.clone());
+                        publisher.publish_ticker(&ticker).await;
+                    }
+                    _ = kline_timer.tick() => {
+                        for kline in market.klines.take_updates() {
+                            publisher.publish_kline(kline).await;
+                        }
+                    }
                 }
             }
 
-        });
+            (progress, market, client_orders)
+        })
     }
 
-    // 处理单个Kafka消息
-    async fn process_kafka_message<C: Consumer>(
-        &self,
-        consumer: &C,
-        message: &BorrowedMessage<'_>
-    ) -> Result<()> {
-        if let Some(payload) = message.payload() {
-            let spot_log: SpotLog = serde_json::from_slice(payload)?;
+    // 处理单个Kafka消息，offset 在检查点写入快照后才保存。
+    // 无法解码的消息同样按顺序交给消息处理器，由其转入死信主题并推进 offset
+    async fn process_kafka_message(&self, message: &BorrowedMessage<'_>) -> Result<()> {
+        let incoming = Incoming {
+            decoded: self.decode(message),
+            source: MessageSource {
+                headers: message.headers().map(|headers| headers.detach()),
+                payload: message.payload().unwrap_or_default().to_vec(),
+                partition: message.partition(),
+                offset: message.offset(),
+                received_at: Instant::now(),
+            },
+        };
 
-            self.spot_log_sender.send(spot_log).await?;
+        let sender = self.spot_log_sender.as_ref()
+            .ok_or_else(|| anyhow!("Engine {} is shutting down", self.symbol))?;
+        sender.send(incoming).await?;
 
-            self.print_order_book().await;
-            if let Err(e) = consumer.store_offset_from_message(message) {
-                async_error!("Failed to store offset: ", e);
-            }
-        }
+        self.print_order_book().await;
         Ok(())
     }
 
+    // 解码输入消息。编码格式取消息头 format，没有该消息头时使用交易对配置的格式
+    fn decode(&self, message: &BorrowedMessage<'_>) -> Result<Envelope, (DeadLetterReason, String)> {
+        let malformed = |error: String| (DeadLetterReason::Malformed, error);
+        let payload = message.payload()
+            .filter(|payload| !payload.is_empty())
+            .ok_or_else(|| malformed("empty payload".to_string()))?;
+        let format = match message.headers().and_then(|headers| headers.iter().find(|header| header.key == MessageFormat::HEADER)) {
+            Some(header) => header.value
+                .and_then(MessageFormat::from_header)
+                .ok_or_else(|| malformed(format!("unknown message format {:?}", header.value.map(String::from_utf8_lossy))))?,
+            None => self.spot_log_format,
+        };
+        let envelope = Envelope::decode(payload, format).map_err(|e| malformed(e.to_string()))?;
+
+        // 版本 1 的消息没有交易对
+        if !envelope.symbol.is_empty() && envelope.symbol != self.symbol {
+            return Err((DeadLetterReason::ValidationFailed, format!("message for {} delivered to {}", envelope.symbol, self.symbol)));
+        }
+        Ok(envelope)
+    }
+
     pub async fn print_order_book(&self) {
         let order_book = self.order_book.lock().await;
         order_book.print_order_book().await;
     }
 }
+
+// 将消息放入生产者队列，不等待投递结果，检查点时统一 flush。消息头 format 标明编码格式
+async fn enqueue(producer: &FutureProducer, topic: &str, key: &str, format: MessageFormat, payload: &[u8]) {
+    let headers = OwnedHeaders::new().insert(Header { key: MessageFormat::HEADER, value: Some(format.as_str()) });
+    enqueue_with_headers(producer, topic, key, headers, payload).await;
+}
+
+async fn enqueue_with_headers(producer: &FutureProducer, topic: &str, key: &str, headers: OwnedHeaders, payload: &[u8]) {
+    if let Err((e, _)) = producer.send_result(FutureRecord::to(topic).key(key).payload(payload).headers(headers)) {
+        metrics::symbol(key).produce_errors.fetch_add(1, Ordering::Relaxed);
+        async_error!(format!("Failed to enqueue message to {}: {}", topic, e));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use once_cell::sync::Lazy;
+    use crate::order::{OrderType, Side};
+
+    // tklog 的日志任务运行在首次记录日志的运行时上，该运行时结束后记录日志会 panic，
+    // 处理器测试因此共用一个运行时
+    static RUNTIME: Lazy<tokio::runtime::Runtime> = Lazy::new(|| tokio::runtime::Runtime::new().unwrap());
+
+    fn incoming(symbol: &str, offset: i64, log_type: LogType, order: Order) -> Incoming {
+        let spot_log = SpotLog { log_type, seq_id: 0, order: Some(order), trade: None, admin: None, report: None };
+        Incoming {
+            decoded: Ok(Envelope::new(symbol, None, None, spot_log)),
+            source: MessageSource { headers: None, payload: Vec::new(), partition: 0, offset, received_at: Instant::now() },
+        }
+    }
+
+    // 备节点的消息处理器，不发布到 Kafka
+    fn processor_parts(symbol: &str, snapshot_dir: &str) -> (Arc<Checkpoint>, Publisher, ProcessorIntervals) {
+        let prefix = symbol.replace('/', "_");
+        let brokers = "localhost:9092";
+        let producer = create_producer(brokers).unwrap();
+        let checkpoint = Arc::new(Checkpoint {
+            symbol: symbol.to_string(),
+            input_topic: format!("{}_SpotNewOrder", prefix),
+            snapshot_dir: snapshot_dir.to_string(),
+            flush_timeout: Duration::from_secs(1),
+            consumer: Arc::new(create_consumer(brokers, &format!("{}_group", prefix), &format!("{}_SpotNewOrder", prefix)).unwrap()),
+            producer: producer.clone(),
+        });
+        let publisher = Publisher {
+            brokers: brokers.to_string(),
+            key: symbol.to_string(),
+            producer,
+            role: EngineRole::Standby,
+            pending_capacity: 100,
+            results: SequencedStream::new(format!("{}_SpotMatchResult", prefix), MessageFormat::Json, true),
+            book_events: None,
+            schema_version: SCHEMA_VERSION,
+            depth_topic: String::new(),
+            depth_format: MessageFormat::Json,
+            ticker_topic: String::new(),
+            kline_topic: String::new(),
+            dead_letter_topic: String::new(),
+            kline_writer: None,
+            persistence: None,
+        };
+        let intervals = ProcessorIntervals {
+            snapshot: Duration::from_secs(60),
+            depth_snapshot: Duration::from_secs(60),
+            ticker: Duration::from_secs(60),
+            kline: Duration::from_secs(60),
+        };
+        (checkpoint, publisher, intervals)
+    }
+
+    #[test]
+    fn test_restored_engine_skips_applied_offsets() {
+        RUNTIME.block_on(async {
+            let symbol = "REPLAY/TEST";
+            let snapshot_dir = std::env::temp_dir().join(format!("spot_match_replay_{}", std::process::id()));
+            let snapshot_dir = snapshot_dir.to_str().unwrap().to_string();
+
+            // 快照中已包含 offset 41 的卖单
+            let resting = Order::new(1, 100.0, 1.0, OrderType::Limit, Side::Sell);
+            let mut order_book = OrderBook::new();
+            order_book.add_order(resting.clone());
+            OrderBookSnapshot::capture(symbol, &order_book, 5, 41).save(&snapshot_dir).await.unwrap();
+
+            let mut engine = Engine::new(symbol.to_string(), "REPLAY".to_string(), "TEST".to_string(), SymbolRules::default());
+            let (_role_sender, role) = watch::channel(EngineRole::Standby);
+            engine.set_role(role);
+            assert!(engine.restore_snapshot(&snapshot_dir).await.unwrap());
+            std::fs::remove_dir_all(&snapshot_dir).unwrap();
+
+            let (checkpoint, publisher, intervals) = processor_parts(symbol, &snapshot_dir);
+            let sender = engine.spot_log_sender.take().unwrap();
+            let processor = engine.start_message_processor(checkpoint, publisher, intervals, -1);
+
+            // 已提交的 offset 落后于快照：重新收到 offset 41 的买单不应再次与快照中的卖单成交
+            sender.send(incoming(symbol, 41, LogType::NewOrder, Order::new(2, 100.0, 1.0, OrderType::Limit, Side::Buy))).await.unwrap();
+            sender.send(incoming(symbol, 42, LogType::NewOrder, Order::new(3, 99.0, 1.0, OrderType::Limit, Side::Buy))).await.unwrap();
+            drop(sender);
+            let (progress, _, _) = processor.await.unwrap();
+
+            assert_eq!(progress.offset, 42);
+            // 只有 offset 42 产生了受理和挂单两条结果
+            assert_eq!(progress.seq_id, 7);
+            let order_book = engine.order_book.lock().await;
+            assert!(order_book.contains(resting.id));
+            assert_eq!(order_book.best_bid(), Some(99.0));
+        });
+    }
+
+    // 以从 exchange_order 恢复的订单簿和已知订单号依次撮合 `messages`，返回最终进度和订单簿
+    async fn run_bootstrapped(symbol: &str, resting: &Order, known_orders: &[u64], messages: Vec<(LogType, Order)>) -> (MatchProgress, Arc<Mutex<OrderBook>>) {
+        let snapshot_dir = std::env::temp_dir().join(format!("spot_match_known_{}", std::process::id()));
+        let mut engine = Engine::new(symbol.to_string(), "KNOWN".to_string(), "TEST".to_string(), SymbolRules::default());
+        engine.order_book = Arc::new(Mutex::new(OrderBook::restore(Vec::new(), vec![resting.clone()])));
+        engine.known_orders = known_orders.iter().copied().collect();
+        let (_role_sender, role) = watch::channel(EngineRole::Standby);
+        engine.set_role(role);
+
+        let (checkpoint, publisher, intervals) = processor_parts(symbol, snapshot_dir.to_str().unwrap());
+        let sender = engine.spot_log_sender.take().unwrap();
+        let processor = engine.start_message_processor(checkpoint, publisher, intervals, -1);
+        for (offset, (log_type, order)) in messages.into_iter().enumerate() {
+            sender.send(incoming(symbol, offset as i64, log_type, order)).await.unwrap();
+        }
+        drop(sender);
+        let (progress, _, _) = processor.await.unwrap();
+        (progress, engine.order_book.clone())
+    }
+
+    #[test]
+    fn test_bootstrapped_engine_skips_replayed_resting_order() {
+        RUNTIME.block_on(async {
+            // exchange_order 中交易中的卖单，重放到它的新订单消息时不视为重复订单，也不再挂单
+            let resting = Order::new(1, 100.0, 1.0, OrderType::Limit, Side::Sell);
+            let (progress, order_book) = run_bootstrapped("RESTING/TEST", &resting, &[resting.id], vec![
+                (LogType::NewOrder, resting.clone()),
+                // 交易中订单的撤单照常处理
+                (LogType::CancelOrder, Order { quantity: 0.4, ..resting.clone() }),
+            ]).await;
+
+            assert_eq!(progress.offset, 1);
+            // 只有部分撤单和撤单回报两条结果，没有拒绝回报，也就没有死信
+            assert_eq!(progress.seq_id, 2);
+            let order_book = order_book.lock().await;
+            assert_eq!(order_book.len(), 1);
+            assert_eq!(order_book.resting_orders().1[0].quantity, 0.6);
+        });
+    }
+
+    #[test]
+    fn test_bootstrapped_engine_skips_finished_orders() {
+        RUNTIME.block_on(async {
+            // exchange_order 中订单 completed 已完成，订单 canceled 已撤销，均不在订单簿中
+            let resting = Order::new(1, 100.0, 1.0, OrderType::Limit, Side::Sell);
+            let completed = Order::new(2, 100.0, 1.0, OrderType::Limit, Side::Buy);
+            let canceled = Order::new(3, 99.0, 1.0, OrderType::Limit, Side::Buy);
+            let (progress, order_book) = run_bootstrapped("FINISHED/TEST", &resting, &[resting.id, completed.id, canceled.id], vec![
+                // 已完成的买单不再与卖单成交
+                (LogType::NewOrder, completed),
+                // 已撤销订单的新订单不再挂单，其撤单不回报拒绝
+                (LogType::NewOrder, canceled.clone()),
+                (LogType::CancelOrder, Order { quantity: 0.0, ..canceled }),
+                // 未知订单照常撮合
+                (LogType::NewOrder, Order::new(4, 99.0, 1.0, OrderType::Limit, Side::Buy)),
+            ]).await;
+
+            assert_eq!(progress.offset, 3);
+            // 只有最后一条新订单的受理和挂单两条结果
+            assert_eq!(progress.seq_id, 2);
+            let order_book = order_book.lock().await;
+            assert_eq!(order_book.best_ask(), Some(100.0));
+            assert_eq!(order_book.best_bid(), Some(99.0));
+            assert_eq!(order_book.len(), 2);
+        });
+    }
+}
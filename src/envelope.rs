@@ -0,0 +1,190 @@
+use anyhow::{anyhow, Result};
+use flatbuffers::FlatBufferBuilder;
+use serde::{Deserialize, Serialize};
+use crate::config::MessageFormat;
+use crate::date::current_timestamp;
+use crate::fbs::envelope_generated::envelope::{
+    envelope_buffer_has_identifier, finish_envelope_buffer, root_as_envelope, Envelope as FbsEnvelope, EnvelopeArgs,
+};
+use crate::spot_log::{LogType, SpotLog};
+
+/// 当前的消息结构版本
+pub const SCHEMA_VERSION: u32 = 2;
+/// 仍能解码的最早版本。版本 1 为不带信封的 SpotLog，解码时补齐信封
+pub const MIN_SCHEMA_VERSION: u32 = 1;
+
+/// 输入订单和撮合结果的信封。结构变更时提高版本号，
+/// 解码端同时兼容上一个版本，生产者和消费者可以分别升级
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Envelope {
+    pub version: u32,                     // 消息结构版本
+    pub message_type: LogType,            // 消息类型，与 body.log_type 相同
+    pub symbol: String,                   // 交易对，版本 1 的消息为空
+    #[serde(default)]
+    pub correlation_id: Option<String>,   // 客户端关联 ID，撮合结果原样带回
+    pub timestamp: u64,                   // 消息生成时间（毫秒）
+    #[serde(default)]
+    pub source_timestamp: Option<u64>,    // 撮合结果对应输入消息的生成时间（毫秒）
+    pub body: SpotLog,
+}
+
+// 只用于读取 JSON 消息的版本号，没有该字段的是版本 1
+#[derive(Deserialize)]
+struct VersionProbe {
+    version: Option<u32>,
+}
+
+impl Envelope {
+    pub fn new(symbol: &str, correlation_id: Option<String>, source_timestamp: Option<u64>, body: SpotLog) -> Self {
+        Envelope {
+            version: SCHEMA_VERSION,
+            message_type: body.log_type,
+            symbol: symbol.to_string(),
+            correlation_id,
+            timestamp: current_timestamp(),
+            source_timestamp,
+            body,
+        }
+    }
+
+    // 版本 1 的消息没有信封，时间取订单、成交、管理操作或执行回报中的时间
+    fn from_legacy(body: SpotLog) -> Self {
+        let timestamp = body.order.as_ref().map(|order| order.timestamp)
+            .or(body.trade.as_ref().map(|trade| trade.timestamp))
+            .or(body.admin.as_ref().map(|admin| admin.timestamp))
+            .or(body.report.as_ref().map(|report| report.timestamp))
+            .unwrap_or_default();
+        Envelope {
+            version: 1,
+            message_type: body.log_type,
+            symbol: String::new(),
+            correlation_id: None,
+            timestamp,
+            source_timestamp: None,
+            body,
+        }
+    }
+
+    /// 按信封的版本和指定格式编码，版本 1 只编码消息体
+    pub fn encode(&self, format: MessageFormat) -> Result<Vec<u8>, serde_json::Error> {
+        match (self.version, format) {
+            (1, _) => self.body.encode(format),
+            (_, MessageFormat::Json) => serde_json::to_vec(self),
+            (_, MessageFormat::Flatbuffers) => Ok(self.to_flatbuffer()),
+        }
+    }
+
+    /// 按指定格式解码，兼容 `MIN_SCHEMA_VERSION` 以来的各版本
+    pub fn decode(payload: &[u8], format: MessageFormat) -> Result<Self> {
+        let envelope = match format {
+            MessageFormat::Json => match serde_json::from_slice::<VersionProbe>(payload)?.version {
+                Some(_) => serde_json::from_slice::<Envelope>(payload)?,
+                None => Self::from_legacy(SpotLog::decode(payload, format)?),
+            },
+            MessageFormat::Flatbuffers if envelope_buffer_has_identifier(payload) => {
+                Self::parse_envelope_flatbuffer(payload).map_err(|e| anyhow!(e))?
+            }
+            MessageFormat::Flatbuffers => Self::from_legacy(SpotLog::decode(payload, format)?),
+        };
+
+        if !(MIN_SCHEMA_VERSION..=SCHEMA_VERSION).contains(&envelope.version) {
+            return Err(anyhow!("Unsupported schema version {}", envelope.version));
+        }
+        if envelope.message_type != envelope.body.log_type {
+            return Err(anyhow!(
+                "Message type {} does not match body log type {}", envelope.message_type, envelope.body.log_type
+            ));
+        }
+        Ok(envelope)
+    }
+
+    /// 从 FlatBuffers 数据解析出 Envelope 实例
+    pub fn parse_envelope_flatbuffer(data: &[u8]) -> Result<Self, &'static str> {
+        let fbs_envelope = root_as_envelope(data).map_err(|_| "Failed to parse FlatBuffer data as Envelope")?;
+        let fbs_body = fbs_envelope.body().ok_or("Missing body in FlatBuffer data")?;
+
+        Ok(Envelope {
+            version: fbs_envelope.version(),
+            message_type: fbs_envelope.message_type().try_into()?,
+            symbol: fbs_envelope.symbol().unwrap_or_default().to_string(),
+            correlation_id: fbs_envelope.correlation_id().map(str::to_string),
+            timestamp: fbs_envelope.timestamp(),
+            source_timestamp: Some(fbs_envelope.source_timestamp()).filter(|timestamp| *timestamp > 0),
+            body: SpotLog::from_flatbuffer(&fbs_body)?,
+        })
+    }
+
+    /// 将 Envelope 实例序列化为 FlatBuffers 格式，带有文件标识
+    pub fn to_flatbuffer(&self) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::with_capacity(512);
+
+        let symbol = builder.create_string(&self.symbol);
+        let correlation_id = self.correlation_id.as_deref().map(|correlation_id| builder.create_string(correlation_id));
+        let body = self.body.create_flatbuffer(&mut builder);
+        let envelope = FbsEnvelope::create(
+            &mut builder,
+            &EnvelopeArgs {
+                version: self.version,
+                message_type: self.message_type.into(),
+                symbol: Some(symbol),
+                correlation_id,
+                timestamp: self.timestamp,
+                source_timestamp: self.source_timestamp.unwrap_or_default(),
+                body: Some(body),
+            },
+        );
+
+        finish_envelope_buffer(&mut builder, envelope);
+
+        builder.finished_data().to_vec()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{Order, OrderType, Side};
+
+    fn new_order() -> SpotLog {
+        SpotLog {
+            log_type: LogType::NewOrder,
+            seq_id: 0,
+            order: Some(Order { id: 7, user_id: 3, price: 101.5, quantity: 2.0, timestamp: 1000, order_type: OrderType::Limit, side: Side::Buy, client_order_id: None, filled_quantity: 0.0 }),
+            trade: None,
+            admin: None,
+            report: None,
+        }
+    }
+
+    #[test]
+    fn test_envelope_versions() {
+        let envelope = Envelope::new("BTC/USDT", Some("client-1".to_string()), Some(900), new_order());
+        for format in [MessageFormat::Json, MessageFormat::Flatbuffers] {
+            // 当前版本
+            let decoded = Envelope::decode(&envelope.encode(format).unwrap(), format).unwrap();
+            assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&envelope).unwrap());
+
+            // 版本 1：不带信封的 SpotLog
+            let legacy = Envelope::decode(&new_order().encode(format).unwrap(), format).unwrap();
+            assert_eq!(legacy.version, 1);
+            assert_eq!(legacy.message_type, LogType::NewOrder);
+            assert_eq!(legacy.symbol, "");
+            assert_eq!(legacy.timestamp, 1000);
+            assert_eq!(legacy.body.order.unwrap().id, 7);
+            let downgraded = Envelope { version: 1, ..envelope.clone() };
+            assert_eq!(Envelope::decode(&downgraded.encode(format).unwrap(), format).unwrap().version, 1);
+
+            // 更新的版本和类型不一致的消息无法解码
+            let newer = Envelope { version: SCHEMA_VERSION + 1, ..envelope.clone() };
+            assert!(Envelope::decode(&newer.encode(format).unwrap(), format).is_err());
+            let mismatched = Envelope { message_type: LogType::CancelOrder, ..envelope.clone() };
+            assert!(Envelope::decode(&mismatched.encode(format).unwrap(), format).is_err());
+        }
+
+        // JSON 中未知的新字段被忽略
+        let mut value = serde_json::to_value(&envelope).unwrap();
+        value["expires_at"] = serde_json::json!(2000);
+        let decoded = Envelope::decode(&serde_json::to_vec(&value).unwrap(), MessageFormat::Json).unwrap();
+        assert_eq!(decoded.correlation_id.as_deref(), Some("client-1"));
+    }
+}
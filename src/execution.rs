@@ -0,0 +1,150 @@
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
+use serde::{Deserialize, Serialize};
+use crate::fbs::spot_log_generated::spot_log::{
+    ExecutionReport as FbsExecutionReport, ExecutionReportArgs, ExecutionStatus as FbsExecutionStatus,
+};
+use crate::order::{Order, OrderType, Side};
+use crate::spot_log::{LogType, SpotLog};
+
+// 订单的执行状态
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum ExecutionStatus {
+    //新订单通过校验，进入撮合
+    Accepted,
+    //新订单或撤单被拒绝
+    Rejected { reason: String },
+    //剩余部分挂入订单簿
+    Resting,
+    //部分成交，主动方和被动方各有一条
+    PartiallyFilled,
+    //全部成交
+    Filled,
+    //撤单，剩余数量大于 0 时为部分撤单
+    Cancelled,
+    //市价单未成交的部分失效
+    Expired,
+}
+
+/// 订单的一次状态变化，作为 LogType::ExecutionReport 的 SpotLog 发布到撮合结果主题，
+/// 客户端按 client_order_id 跟踪自己的订单
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionReport {
+    pub order_id: u64,
+    #[serde(default)]
+    pub client_order_id: Option<String>,
+    pub user_id: u64,
+    pub side: Side,
+    pub order_type: OrderType,
+    pub price: f64,                  // 委托价格
+    #[serde(flatten)]
+    pub status: ExecutionStatus,
+    pub last_quantity: f64,          // 本次成交、撤销或失效的数量
+    pub last_price: f64,             // 本次成交价格，非成交时为 0
+    pub cumulative_quantity: f64,    // 累计成交数量
+    pub remaining_quantity: f64,     // 剩余未成交的挂单数量，订单结束时为 0
+    pub timestamp: u64,              // 毫秒级时间戳，取自产生回报的输入消息
+}
+
+impl ExecutionReport {
+    /// 订单当前的状态，累计成交数量取自 `order.filled_quantity`
+    pub fn new(order: &Order, status: ExecutionStatus, remaining_quantity: f64) -> Self {
+        ExecutionReport {
+            order_id: order.id,
+            client_order_id: order.client_order_id.clone(),
+            user_id: order.user_id,
+            side: order.side,
+            order_type: order.order_type,
+            price: order.price,
+            status,
+            last_quantity: 0.0,
+            last_price: 0.0,
+            cumulative_quantity: order.filled_quantity,
+            remaining_quantity,
+            timestamp: order.timestamp,
+        }
+    }
+
+    /// 新订单或撤单被拒绝，订单就此结束
+    pub fn rejected(order: &Order, reason: String) -> Self {
+        ExecutionReport { cumulative_quantity: 0.0, ..Self::new(order, ExecutionStatus::Rejected { reason }, 0.0) }
+    }
+
+    /// 记录本次成交、撤销或失效的数量和价格
+    pub fn with_last(self, quantity: f64, price: f64) -> Self {
+        ExecutionReport { last_quantity: quantity, last_price: price, ..self }
+    }
+
+    pub fn into_spot_log(self) -> SpotLog {
+        SpotLog {
+            log_type: LogType::ExecutionReport,
+            seq_id: 0,
+            order: None,
+            trade: None,
+            admin: None,
+            report: Some(self),
+        }
+    }
+
+    /// 将 FlatBuffers 中的 ExecutionReport 表转换成 ExecutionReport
+    pub fn from_flatbuffer(fbs_report: &FbsExecutionReport) -> Result<Self, &'static str> {
+        let status = match fbs_report.status() {
+            FbsExecutionStatus::Accepted => ExecutionStatus::Accepted,
+            FbsExecutionStatus::Rejected => ExecutionStatus::Rejected {
+                reason: fbs_report.reject_reason().unwrap_or_default().to_string(),
+            },
+            FbsExecutionStatus::Resting => ExecutionStatus::Resting,
+            FbsExecutionStatus::PartiallyFilled => ExecutionStatus::PartiallyFilled,
+            FbsExecutionStatus::Filled => ExecutionStatus::Filled,
+            FbsExecutionStatus::Cancelled => ExecutionStatus::Cancelled,
+            FbsExecutionStatus::Expired => ExecutionStatus::Expired,
+            _ => return Err("Invalid execution status in FlatBuffer data"),
+        };
+        Ok(ExecutionReport {
+            order_id: fbs_report.order_id(),
+            client_order_id: fbs_report.client_order_id().map(str::to_string),
+            user_id: fbs_report.user_id(),
+            side: fbs_report.side().try_into()?,
+            order_type: fbs_report.order_type().try_into()?,
+            price: fbs_report.price(),
+            status,
+            last_quantity: fbs_report.last_quantity(),
+            last_price: fbs_report.last_price(),
+            cumulative_quantity: fbs_report.cumulative_quantity(),
+            remaining_quantity: fbs_report.remaining_quantity(),
+            timestamp: fbs_report.timestamp(),
+        })
+    }
+
+    /// 在 builder 中写入 ExecutionReport 表，返回其偏移，供外层表引用
+    pub fn create_flatbuffer<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<FbsExecutionReport<'a>> {
+        let client_order_id = self.client_order_id.as_deref().map(|client_order_id| builder.create_string(client_order_id));
+        let (status, reject_reason) = match &self.status {
+            ExecutionStatus::Accepted => (FbsExecutionStatus::Accepted, None),
+            ExecutionStatus::Rejected { reason } => (FbsExecutionStatus::Rejected, Some(builder.create_string(reason))),
+            ExecutionStatus::Resting => (FbsExecutionStatus::Resting, None),
+            ExecutionStatus::PartiallyFilled => (FbsExecutionStatus::PartiallyFilled, None),
+            ExecutionStatus::Filled => (FbsExecutionStatus::Filled, None),
+            ExecutionStatus::Cancelled => (FbsExecutionStatus::Cancelled, None),
+            ExecutionStatus::Expired => (FbsExecutionStatus::Expired, None),
+        };
+        FbsExecutionReport::create(
+            builder,
+            &ExecutionReportArgs {
+                order_id: self.order_id,
+                client_order_id,
+                user_id: self.user_id,
+                side: self.side.into(),
+                order_type: self.order_type.into(),
+                price: self.price,
+                status,
+                reject_reason,
+                last_quantity: self.last_quantity,
+                last_price: self.last_price,
+                cumulative_quantity: self.cumulative_quantity,
+                remaining_quantity: self.remaining_quantity,
+                timestamp: self.timestamp,
+            },
+        )
+    }
+}
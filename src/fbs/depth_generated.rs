@@ -0,0 +1,486 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+
+// @generated
+
+extern crate flatbuffers;
+
+#[allow(unused_imports, dead_code)]
+pub mod depth {
+
+  use core::mem;
+  use core::cmp::Ordering;
+
+  extern crate flatbuffers;
+  use self::flatbuffers::{EndianScalar, Follow};
+
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_DEPTH_KIND: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_DEPTH_KIND: i8 = 1;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_DEPTH_KIND: [DepthKind; 2] = [
+  DepthKind::Update,
+  DepthKind::Snapshot,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct DepthKind(pub i8);
+#[allow(non_upper_case_globals)]
+impl DepthKind {
+  pub const Update: Self = Self(0);
+  pub const Snapshot: Self = Self(1);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 1;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Update,
+    Self::Snapshot,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Update => Some("Update"),
+      Self::Snapshot => Some("Snapshot"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for DepthKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for DepthKind {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for DepthKind {
+    type Output = DepthKind;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for DepthKind {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for DepthKind {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for DepthKind {}
+pub enum PriceLevelOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct PriceLevel<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for PriceLevel<'a> {
+  type Inner = PriceLevel<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> PriceLevel<'a> {
+  pub const VT_PRICE: flatbuffers::VOffsetT = 4;
+  pub const VT_QUANTITY: flatbuffers::VOffsetT = 6;
+  pub const VT_ORDER_COUNT: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    PriceLevel { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args PriceLevelArgs
+  ) -> flatbuffers::WIPOffset<PriceLevel<'bldr>> {
+    let mut builder = PriceLevelBuilder::new(_fbb);
+    builder.add_order_count(args.order_count);
+    builder.add_quantity(args.quantity);
+    builder.add_price(args.price);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn price(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(PriceLevel::VT_PRICE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn quantity(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(PriceLevel::VT_QUANTITY, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn order_count(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(PriceLevel::VT_ORDER_COUNT, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for PriceLevel<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<f64>("price", Self::VT_PRICE, false)?
+     .visit_field::<f64>("quantity", Self::VT_QUANTITY, false)?
+     .visit_field::<u64>("order_count", Self::VT_ORDER_COUNT, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct PriceLevelArgs {
+    pub price: f64,
+    pub quantity: f64,
+    pub order_count: u64,
+}
+impl<'a> Default for PriceLevelArgs {
+  #[inline]
+  fn default() -> Self {
+    PriceLevelArgs {
+      price: 0.0,
+      quantity: 0.0,
+      order_count: 0,
+    }
+  }
+}
+
+pub struct PriceLevelBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> PriceLevelBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_price(&mut self, price: f64) {
+    self.fbb_.push_slot::<f64>(PriceLevel::VT_PRICE, price, 0.0);
+  }
+  #[inline]
+  pub fn add_quantity(&mut self, quantity: f64) {
+    self.fbb_.push_slot::<f64>(PriceLevel::VT_QUANTITY, quantity, 0.0);
+  }
+  #[inline]
+  pub fn add_order_count(&mut self, order_count: u64) {
+    self.fbb_.push_slot::<u64>(PriceLevel::VT_ORDER_COUNT, order_count, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> PriceLevelBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    PriceLevelBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<PriceLevel<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for PriceLevel<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("PriceLevel");
+      ds.field("price", &self.price());
+      ds.field("quantity", &self.quantity());
+      ds.field("order_count", &self.order_count());
+      ds.finish()
+  }
+}
+pub enum DepthUpdateOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct DepthUpdate<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for DepthUpdate<'a> {
+  type Inner = DepthUpdate<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> DepthUpdate<'a> {
+  pub const VT_KIND: flatbuffers::VOffsetT = 4;
+  pub const VT_SYMBOL: flatbuffers::VOffsetT = 6;
+  pub const VT_SEQ_ID: flatbuffers::VOffsetT = 8;
+  pub const VT_TIMESTAMP: flatbuffers::VOffsetT = 10;
+  pub const VT_BIDS: flatbuffers::VOffsetT = 12;
+  pub const VT_ASKS: flatbuffers::VOffsetT = 14;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    DepthUpdate { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args DepthUpdateArgs<'args>
+  ) -> flatbuffers::WIPOffset<DepthUpdate<'bldr>> {
+    let mut builder = DepthUpdateBuilder::new(_fbb);
+    builder.add_timestamp(args.timestamp);
+    builder.add_seq_id(args.seq_id);
+    if let Some(x) = args.asks { builder.add_asks(x); }
+    if let Some(x) = args.bids { builder.add_bids(x); }
+    if let Some(x) = args.symbol { builder.add_symbol(x); }
+    builder.add_kind(args.kind);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn kind(&self) -> DepthKind {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<DepthKind>(DepthUpdate::VT_KIND, Some(DepthKind::Update)).unwrap()}
+  }
+  #[inline]
+  pub fn symbol(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(DepthUpdate::VT_SYMBOL, None)}
+  }
+  #[inline]
+  pub fn seq_id(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DepthUpdate::VT_SEQ_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn timestamp(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(DepthUpdate::VT_TIMESTAMP, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn bids(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel>>>>(DepthUpdate::VT_BIDS, None)}
+  }
+  #[inline]
+  pub fn asks(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel>>>>(DepthUpdate::VT_ASKS, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for DepthUpdate<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<DepthKind>("kind", Self::VT_KIND, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("symbol", Self::VT_SYMBOL, false)?
+     .visit_field::<u64>("seq_id", Self::VT_SEQ_ID, false)?
+     .visit_field::<u64>("timestamp", Self::VT_TIMESTAMP, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<PriceLevel>>>>("bids", Self::VT_BIDS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<PriceLevel>>>>("asks", Self::VT_ASKS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct DepthUpdateArgs<'a> {
+    pub kind: DepthKind,
+    pub symbol: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub seq_id: u64,
+    pub timestamp: u64,
+    pub bids: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel<'a>>>>>,
+    pub asks: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<PriceLevel<'a>>>>>,
+}
+impl<'a> Default for DepthUpdateArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    DepthUpdateArgs {
+      kind: DepthKind::Update,
+      symbol: None,
+      seq_id: 0,
+      timestamp: 0,
+      bids: None,
+      asks: None,
+    }
+  }
+}
+
+pub struct DepthUpdateBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> DepthUpdateBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_kind(&mut self, kind: DepthKind) {
+    self.fbb_.push_slot::<DepthKind>(DepthUpdate::VT_KIND, kind, DepthKind::Update);
+  }
+  #[inline]
+  pub fn add_symbol(&mut self, symbol: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DepthUpdate::VT_SYMBOL, symbol);
+  }
+  #[inline]
+  pub fn add_seq_id(&mut self, seq_id: u64) {
+    self.fbb_.push_slot::<u64>(DepthUpdate::VT_SEQ_ID, seq_id, 0);
+  }
+  #[inline]
+  pub fn add_timestamp(&mut self, timestamp: u64) {
+    self.fbb_.push_slot::<u64>(DepthUpdate::VT_TIMESTAMP, timestamp, 0);
+  }
+  #[inline]
+  pub fn add_bids(&mut self, bids: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<PriceLevel<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DepthUpdate::VT_BIDS, bids);
+  }
+  #[inline]
+  pub fn add_asks(&mut self, asks: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<PriceLevel<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(DepthUpdate::VT_ASKS, asks);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> DepthUpdateBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    DepthUpdateBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<DepthUpdate<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for DepthUpdate<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("DepthUpdate");
+      ds.field("kind", &self.kind());
+      ds.field("symbol", &self.symbol());
+      ds.field("seq_id", &self.seq_id());
+      ds.field("timestamp", &self.timestamp());
+      ds.field("bids", &self.bids());
+      ds.field("asks", &self.asks());
+      ds.finish()
+  }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `DepthUpdate`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_depth_update_unchecked`.
+pub fn root_as_depth_update(buf: &[u8]) -> Result<DepthUpdate, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<DepthUpdate>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `DepthUpdate` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_depth_update_unchecked`.
+pub fn size_prefixed_root_as_depth_update(buf: &[u8]) -> Result<DepthUpdate, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<DepthUpdate>(buf)
+}
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `DepthUpdate` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_depth_update_unchecked`.
+pub fn root_as_depth_update_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<DepthUpdate<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<DepthUpdate<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `DepthUpdate` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_depth_update_unchecked`.
+pub fn size_prefixed_root_as_depth_update_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<DepthUpdate<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<DepthUpdate<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a DepthUpdate and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `DepthUpdate`.
+pub unsafe fn root_as_depth_update_unchecked(buf: &[u8]) -> DepthUpdate {
+  flatbuffers::root_unchecked::<DepthUpdate>(buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed DepthUpdate and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `DepthUpdate`.
+pub unsafe fn size_prefixed_root_as_depth_update_unchecked(buf: &[u8]) -> DepthUpdate {
+  flatbuffers::size_prefixed_root_unchecked::<DepthUpdate>(buf)
+}
+#[inline]
+pub fn finish_depth_update_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+    root: flatbuffers::WIPOffset<DepthUpdate<'a>>) {
+  fbb.finish(root, None);
+}
+
+#[inline]
+pub fn finish_size_prefixed_depth_update_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>, root: flatbuffers::WIPOffset<DepthUpdate<'a>>) {
+  fbb.finish_size_prefixed(root, None);
+}
+}  // pub mod depth
@@ -0,0 +1,303 @@
+// automatically generated by the FlatBuffers compiler, do not modify
+
+
+// @generated
+
+use crate::fbs::spot_log_generated::*;
+
+extern crate flatbuffers;
+
+#[allow(unused_imports, dead_code)]
+pub mod envelope {
+
+  use crate::fbs::spot_log_generated::*;
+  use core::mem;
+  use core::cmp::Ordering;
+
+  extern crate flatbuffers;
+  use self::flatbuffers::{EndianScalar, Follow};
+
+pub enum EnvelopeOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Envelope<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Envelope<'a> {
+  type Inner = Envelope<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Envelope<'a> {
+  pub const VT_VERSION: flatbuffers::VOffsetT = 4;
+  pub const VT_MESSAGE_TYPE: flatbuffers::VOffsetT = 6;
+  pub const VT_SYMBOL: flatbuffers::VOffsetT = 8;
+  pub const VT_CORRELATION_ID: flatbuffers::VOffsetT = 10;
+  pub const VT_TIMESTAMP: flatbuffers::VOffsetT = 12;
+  pub const VT_SOURCE_TIMESTAMP: flatbuffers::VOffsetT = 14;
+  pub const VT_BODY: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Envelope { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args EnvelopeArgs<'args>
+  ) -> flatbuffers::WIPOffset<Envelope<'bldr>> {
+    let mut builder = EnvelopeBuilder::new(_fbb);
+    builder.add_source_timestamp(args.source_timestamp);
+    builder.add_timestamp(args.timestamp);
+    if let Some(x) = args.body { builder.add_body(x); }
+    if let Some(x) = args.correlation_id { builder.add_correlation_id(x); }
+    if let Some(x) = args.symbol { builder.add_symbol(x); }
+    builder.add_version(args.version);
+    builder.add_message_type(args.message_type);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn version(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Envelope::VT_VERSION, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn message_type(&self) -> super::spot_log::LogType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<super::spot_log::LogType>(Envelope::VT_MESSAGE_TYPE, Some(super::spot_log::LogType::NewOrder)).unwrap()}
+  }
+  #[inline]
+  pub fn symbol(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Envelope::VT_SYMBOL, None)}
+  }
+  #[inline]
+  pub fn correlation_id(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Envelope::VT_CORRELATION_ID, None)}
+  }
+  #[inline]
+  pub fn timestamp(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Envelope::VT_TIMESTAMP, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn source_timestamp(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Envelope::VT_SOURCE_TIMESTAMP, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn body(&self) -> Option<super::spot_log::SpotLog<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<super::spot_log::SpotLog>>(Envelope::VT_BODY, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for Envelope<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u32>("version", Self::VT_VERSION, false)?
+     .visit_field::<super::spot_log::LogType>("message_type", Self::VT_MESSAGE_TYPE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("symbol", Self::VT_SYMBOL, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("correlation_id", Self::VT_CORRELATION_ID, false)?
+     .visit_field::<u64>("timestamp", Self::VT_TIMESTAMP, false)?
+     .visit_field::<u64>("source_timestamp", Self::VT_SOURCE_TIMESTAMP, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<super::spot_log::SpotLog>>("body", Self::VT_BODY, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct EnvelopeArgs<'a> {
+    pub version: u32,
+    pub message_type: super::spot_log::LogType,
+    pub symbol: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub correlation_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub timestamp: u64,
+    pub source_timestamp: u64,
+    pub body: Option<flatbuffers::WIPOffset<super::spot_log::SpotLog<'a>>>,
+}
+impl<'a> Default for EnvelopeArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    EnvelopeArgs {
+      version: 0,
+      message_type: super::spot_log::LogType::NewOrder,
+      symbol: None,
+      correlation_id: None,
+      timestamp: 0,
+      source_timestamp: 0,
+      body: None,
+    }
+  }
+}
+
+pub struct EnvelopeBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> EnvelopeBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_version(&mut self, version: u32) {
+    self.fbb_.push_slot::<u32>(Envelope::VT_VERSION, version, 0);
+  }
+  #[inline]
+  pub fn add_message_type(&mut self, message_type: super::spot_log::LogType) {
+    self.fbb_.push_slot::<super::spot_log::LogType>(Envelope::VT_MESSAGE_TYPE, message_type, super::spot_log::LogType::NewOrder);
+  }
+  #[inline]
+  pub fn add_symbol(&mut self, symbol: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Envelope::VT_SYMBOL, symbol);
+  }
+  #[inline]
+  pub fn add_correlation_id(&mut self, correlation_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Envelope::VT_CORRELATION_ID, correlation_id);
+  }
+  #[inline]
+  pub fn add_timestamp(&mut self, timestamp: u64) {
+    self.fbb_.push_slot::<u64>(Envelope::VT_TIMESTAMP, timestamp, 0);
+  }
+  #[inline]
+  pub fn add_source_timestamp(&mut self, source_timestamp: u64) {
+    self.fbb_.push_slot::<u64>(Envelope::VT_SOURCE_TIMESTAMP, source_timestamp, 0);
+  }
+  #[inline]
+  pub fn add_body(&mut self, body: flatbuffers::WIPOffset<super::spot_log::SpotLog<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<super::spot_log::SpotLog>>(Envelope::VT_BODY, body);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> EnvelopeBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    EnvelopeBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Envelope<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Envelope<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Envelope");
+      ds.field("version", &self.version());
+      ds.field("message_type", &self.message_type());
+      ds.field("symbol", &self.symbol());
+      ds.field("correlation_id", &self.correlation_id());
+      ds.field("timestamp", &self.timestamp());
+      ds.field("source_timestamp", &self.source_timestamp());
+      ds.field("body", &self.body());
+      ds.finish()
+  }
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a `Envelope`
+/// and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_envelope_unchecked`.
+pub fn root_as_envelope(buf: &[u8]) -> Result<Envelope, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root::<Envelope>(buf)
+}
+#[inline]
+/// Verifies that a buffer of bytes contains a size prefixed
+/// `Envelope` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `size_prefixed_root_as_envelope_unchecked`.
+pub fn size_prefixed_root_as_envelope(buf: &[u8]) -> Result<Envelope, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root::<Envelope>(buf)
+}
+#[inline]
+/// Verifies, with the given options, that a buffer of bytes
+/// contains a `Envelope` and returns it.
+/// Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_envelope_unchecked`.
+pub fn root_as_envelope_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<Envelope<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::root_with_opts::<Envelope<'b>>(opts, buf)
+}
+#[inline]
+/// Verifies, with the given verifier options, that a buffer of
+/// bytes contains a size prefixed `Envelope` and returns
+/// it. Note that verification is still experimental and may not
+/// catch every error, or be maximally performant. For the
+/// previous, unchecked, behavior use
+/// `root_as_envelope_unchecked`.
+pub fn size_prefixed_root_as_envelope_with_opts<'b, 'o>(
+  opts: &'o flatbuffers::VerifierOptions,
+  buf: &'b [u8],
+) -> Result<Envelope<'b>, flatbuffers::InvalidFlatbuffer> {
+  flatbuffers::size_prefixed_root_with_opts::<Envelope<'b>>(opts, buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a Envelope and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid `Envelope`.
+pub unsafe fn root_as_envelope_unchecked(buf: &[u8]) -> Envelope {
+  flatbuffers::root_unchecked::<Envelope>(buf)
+}
+#[inline]
+/// Assumes, without verification, that a buffer of bytes contains a size prefixed Envelope and returns it.
+/// # Safety
+/// Callers must trust the given bytes do indeed contain a valid size prefixed `Envelope`.
+pub unsafe fn size_prefixed_root_as_envelope_unchecked(buf: &[u8]) -> Envelope {
+  flatbuffers::size_prefixed_root_unchecked::<Envelope>(buf)
+}
+pub const ENVELOPE_IDENTIFIER: &str = "SMEV";
+
+#[inline]
+pub fn envelope_buffer_has_identifier(buf: &[u8]) -> bool {
+  flatbuffers::buffer_has_identifier(buf, ENVELOPE_IDENTIFIER, false)
+}
+
+#[inline]
+pub fn envelope_size_prefixed_buffer_has_identifier(buf: &[u8]) -> bool {
+  flatbuffers::buffer_has_identifier(buf, ENVELOPE_IDENTIFIER, true)
+}
+
+#[inline]
+pub fn finish_envelope_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(
+    fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+    root: flatbuffers::WIPOffset<Envelope<'a>>) {
+  fbb.finish(root, Some(ENVELOPE_IDENTIFIER));
+}
+
+#[inline]
+pub fn finish_size_prefixed_envelope_buffer<'a, 'b, A: flatbuffers::Allocator + 'a>(fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>, root: flatbuffers::WIPOffset<Envelope<'a>>) {
+  fbb.finish_size_prefixed(root, Some(ENVELOPE_IDENTIFIER));
+}
+}  // pub mod envelope
+
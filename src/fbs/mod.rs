@@ -1,5 +1,15 @@
 // 引入自动生成的 `order_generated.rs` 文件
+// *_generated.rs 由同目录下的 .fbs 经 flatc 24.3.25 生成，修改 schema 后执行 scripts/generate_fbs.sh 重新生成，不要手工修改
+#[allow(mismatched_lifetime_syntaxes, clippy::all)]
 pub mod order_generated;
+#[allow(mismatched_lifetime_syntaxes, clippy::all)]
 pub mod spot_log_generated;
 
+#[allow(mismatched_lifetime_syntaxes, clippy::all)]
 pub mod trade_generated;
+
+#[allow(mismatched_lifetime_syntaxes, clippy::all)]
+pub mod depth_generated;
+
+#[allow(mismatched_lifetime_syntaxes, clippy::all)]
+pub mod envelope_generated;
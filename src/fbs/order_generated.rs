@@ -208,6 +208,8 @@ impl<'a> Order<'a> {
   pub const VT_TIMESTAMP: flatbuffers::VOffsetT = 12;
   pub const VT_ORDER_TYPE: flatbuffers::VOffsetT = 14;
   pub const VT_SIDE: flatbuffers::VOffsetT = 16;
+  pub const VT_CLIENT_ORDER_ID: flatbuffers::VOffsetT = 18;
+  pub const VT_FILLED_QUANTITY: flatbuffers::VOffsetT = 20;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -216,14 +218,16 @@ impl<'a> Order<'a> {
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args OrderArgs
+    args: &'args OrderArgs<'args>
   ) -> flatbuffers::WIPOffset<Order<'bldr>> {
     let mut builder = OrderBuilder::new(_fbb);
+    builder.add_filled_quantity(args.filled_quantity);
     builder.add_timestamp(args.timestamp);
     builder.add_quantity(args.quantity);
     builder.add_price(args.price);
     builder.add_user_id(args.user_id);
     builder.add_id(args.id);
+    if let Some(x) = args.client_order_id { builder.add_client_order_id(x); }
     builder.add_side(args.side);
     builder.add_order_type(args.order_type);
     builder.finish()
@@ -279,6 +283,20 @@ impl<'a> Order<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<Side>(Order::VT_SIDE, Some(Side::Buy)).unwrap()}
   }
+  #[inline]
+  pub fn client_order_id(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Order::VT_CLIENT_ORDER_ID, None)}
+  }
+  #[inline]
+  pub fn filled_quantity(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(Order::VT_FILLED_QUANTITY, Some(0.0)).unwrap()}
+  }
 }
 
 impl flatbuffers::Verifiable for Order<'_> {
@@ -295,11 +313,13 @@ impl flatbuffers::Verifiable for Order<'_> {
      .visit_field::<u64>("timestamp", Self::VT_TIMESTAMP, false)?
      .visit_field::<OrderType>("order_type", Self::VT_ORDER_TYPE, false)?
      .visit_field::<Side>("side", Self::VT_SIDE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("client_order_id", Self::VT_CLIENT_ORDER_ID, false)?
+     .visit_field::<f64>("filled_quantity", Self::VT_FILLED_QUANTITY, false)?
      .finish();
     Ok(())
   }
 }
-pub struct OrderArgs {
+pub struct OrderArgs<'a> {
     pub id: u64,
     pub user_id: u64,
     pub price: f64,
@@ -307,8 +327,10 @@ pub struct OrderArgs {
     pub timestamp: u64,
     pub order_type: OrderType,
     pub side: Side,
+    pub client_order_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub filled_quantity: f64,
 }
-impl<'a> Default for OrderArgs {
+impl<'a> Default for OrderArgs<'a> {
   #[inline]
   fn default() -> Self {
     OrderArgs {
@@ -319,6 +341,8 @@ impl<'a> Default for OrderArgs {
       timestamp: 0,
       order_type: OrderType::Limit,
       side: Side::Buy,
+      client_order_id: None,
+      filled_quantity: 0.0,
     }
   }
 }
@@ -357,6 +381,14 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> OrderBuilder<'a, 'b, A> {
     self.fbb_.push_slot::<Side>(Order::VT_SIDE, side, Side::Buy);
   }
   #[inline]
+  pub fn add_client_order_id(&mut self, client_order_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Order::VT_CLIENT_ORDER_ID, client_order_id);
+  }
+  #[inline]
+  pub fn add_filled_quantity(&mut self, filled_quantity: f64) {
+    self.fbb_.push_slot::<f64>(Order::VT_FILLED_QUANTITY, filled_quantity, 0.0);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> OrderBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     OrderBuilder {
@@ -381,6 +413,8 @@ impl core::fmt::Debug for Order<'_> {
       ds.field("timestamp", &self.timestamp());
       ds.field("order_type", &self.order_type());
       ds.field("side", &self.side());
+      ds.field("client_order_id", &self.client_order_id());
+      ds.field("filled_quantity", &self.filled_quantity());
       ds.finish()
   }
 }
@@ -22,13 +22,15 @@ pub mod spot_log {
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 pub const ENUM_MIN_LOG_TYPE: i8 = 0;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
-pub const ENUM_MAX_LOG_TYPE: i8 = 2;
+pub const ENUM_MAX_LOG_TYPE: i8 = 4;
 #[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
 #[allow(non_camel_case_types)]
-pub const ENUM_VALUES_LOG_TYPE: [LogType; 3] = [
+pub const ENUM_VALUES_LOG_TYPE: [LogType; 5] = [
   LogType::NewOrder,
   LogType::CancelOrder,
   LogType::Trade,
+  LogType::Admin,
+  LogType::ExecutionReport,
 ];
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -39,13 +41,17 @@ impl LogType {
   pub const NewOrder: Self = Self(0);
   pub const CancelOrder: Self = Self(1);
   pub const Trade: Self = Self(2);
+  pub const Admin: Self = Self(3);
+  pub const ExecutionReport: Self = Self(4);
 
   pub const ENUM_MIN: i8 = 0;
-  pub const ENUM_MAX: i8 = 2;
+  pub const ENUM_MAX: i8 = 4;
   pub const ENUM_VALUES: &'static [Self] = &[
     Self::NewOrder,
     Self::CancelOrder,
     Self::Trade,
+    Self::Admin,
+    Self::ExecutionReport,
   ];
   /// Returns the variant's name or "" if unknown.
   pub fn variant_name(self) -> Option<&'static str> {
@@ -53,6 +59,8 @@ impl LogType {
       Self::NewOrder => Some("NewOrder"),
       Self::CancelOrder => Some("CancelOrder"),
       Self::Trade => Some("Trade"),
+      Self::Admin => Some("Admin"),
+      Self::ExecutionReport => Some("ExecutionReport"),
       _ => None,
     }
   }
@@ -108,6 +116,813 @@ impl<'a> flatbuffers::Verifiable for LogType {
 }
 
 impl flatbuffers::SimpleToVerifyInSlice for LogType {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_ADMIN_COMMAND: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_ADMIN_COMMAND: i8 = 4;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_ADMIN_COMMAND: [AdminCommand; 5] = [
+  AdminCommand::Halt,
+  AdminCommand::Resume,
+  AdminCommand::CancelAll,
+  AdminCommand::Snapshot,
+  AdminCommand::SetLogLevel,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct AdminCommand(pub i8);
+#[allow(non_upper_case_globals)]
+impl AdminCommand {
+  pub const Halt: Self = Self(0);
+  pub const Resume: Self = Self(1);
+  pub const CancelAll: Self = Self(2);
+  pub const Snapshot: Self = Self(3);
+  pub const SetLogLevel: Self = Self(4);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 4;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Halt,
+    Self::Resume,
+    Self::CancelAll,
+    Self::Snapshot,
+    Self::SetLogLevel,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Halt => Some("Halt"),
+      Self::Resume => Some("Resume"),
+      Self::CancelAll => Some("CancelAll"),
+      Self::Snapshot => Some("Snapshot"),
+      Self::SetLogLevel => Some("SetLogLevel"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for AdminCommand {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for AdminCommand {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for AdminCommand {
+    type Output = AdminCommand;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for AdminCommand {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for AdminCommand {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for AdminCommand {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_LOG_LEVEL: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_LOG_LEVEL: i8 = 6;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_LOG_LEVEL: [LogLevel; 7] = [
+  LogLevel::Trace,
+  LogLevel::Debug,
+  LogLevel::Info,
+  LogLevel::Warn,
+  LogLevel::Error,
+  LogLevel::Fatal,
+  LogLevel::Off,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct LogLevel(pub i8);
+#[allow(non_upper_case_globals)]
+impl LogLevel {
+  pub const Trace: Self = Self(0);
+  pub const Debug: Self = Self(1);
+  pub const Info: Self = Self(2);
+  pub const Warn: Self = Self(3);
+  pub const Error: Self = Self(4);
+  pub const Fatal: Self = Self(5);
+  pub const Off: Self = Self(6);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 6;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Trace,
+    Self::Debug,
+    Self::Info,
+    Self::Warn,
+    Self::Error,
+    Self::Fatal,
+    Self::Off,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Trace => Some("Trace"),
+      Self::Debug => Some("Debug"),
+      Self::Info => Some("Info"),
+      Self::Warn => Some("Warn"),
+      Self::Error => Some("Error"),
+      Self::Fatal => Some("Fatal"),
+      Self::Off => Some("Off"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for LogLevel {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for LogLevel {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for LogLevel {
+    type Output = LogLevel;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for LogLevel {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for LogLevel {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for LogLevel {}
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MIN_EXECUTION_STATUS: i8 = 0;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+pub const ENUM_MAX_EXECUTION_STATUS: i8 = 6;
+#[deprecated(since = "2.0.0", note = "Use associated constants instead. This will no longer be generated in 2021.")]
+#[allow(non_camel_case_types)]
+pub const ENUM_VALUES_EXECUTION_STATUS: [ExecutionStatus; 7] = [
+  ExecutionStatus::Accepted,
+  ExecutionStatus::Rejected,
+  ExecutionStatus::Resting,
+  ExecutionStatus::PartiallyFilled,
+  ExecutionStatus::Filled,
+  ExecutionStatus::Cancelled,
+  ExecutionStatus::Expired,
+];
+
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[repr(transparent)]
+pub struct ExecutionStatus(pub i8);
+#[allow(non_upper_case_globals)]
+impl ExecutionStatus {
+  pub const Accepted: Self = Self(0);
+  pub const Rejected: Self = Self(1);
+  pub const Resting: Self = Self(2);
+  pub const PartiallyFilled: Self = Self(3);
+  pub const Filled: Self = Self(4);
+  pub const Cancelled: Self = Self(5);
+  pub const Expired: Self = Self(6);
+
+  pub const ENUM_MIN: i8 = 0;
+  pub const ENUM_MAX: i8 = 6;
+  pub const ENUM_VALUES: &'static [Self] = &[
+    Self::Accepted,
+    Self::Rejected,
+    Self::Resting,
+    Self::PartiallyFilled,
+    Self::Filled,
+    Self::Cancelled,
+    Self::Expired,
+  ];
+  /// Returns the variant's name or "" if unknown.
+  pub fn variant_name(self) -> Option<&'static str> {
+    match self {
+      Self::Accepted => Some("Accepted"),
+      Self::Rejected => Some("Rejected"),
+      Self::Resting => Some("Resting"),
+      Self::PartiallyFilled => Some("PartiallyFilled"),
+      Self::Filled => Some("Filled"),
+      Self::Cancelled => Some("Cancelled"),
+      Self::Expired => Some("Expired"),
+      _ => None,
+    }
+  }
+}
+impl core::fmt::Debug for ExecutionStatus {
+  fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+    if let Some(name) = self.variant_name() {
+      f.write_str(name)
+    } else {
+      f.write_fmt(format_args!("<UNKNOWN {:?}>", self.0))
+    }
+  }
+}
+impl<'a> flatbuffers::Follow<'a> for ExecutionStatus {
+  type Inner = Self;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    let b = flatbuffers::read_scalar_at::<i8>(buf, loc);
+    Self(b)
+  }
+}
+
+impl flatbuffers::Push for ExecutionStatus {
+    type Output = ExecutionStatus;
+    #[inline]
+    unsafe fn push(&self, dst: &mut [u8], _written_len: usize) {
+        flatbuffers::emplace_scalar::<i8>(dst, self.0);
+    }
+}
+
+impl flatbuffers::EndianScalar for ExecutionStatus {
+  type Scalar = i8;
+  #[inline]
+  fn to_little_endian(self) -> i8 {
+    self.0.to_le()
+  }
+  #[inline]
+  #[allow(clippy::wrong_self_convention)]
+  fn from_little_endian(v: i8) -> Self {
+    let b = i8::from_le(v);
+    Self(b)
+  }
+}
+
+impl<'a> flatbuffers::Verifiable for ExecutionStatus {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    i8::run_verifier(v, pos)
+  }
+}
+
+impl flatbuffers::SimpleToVerifyInSlice for ExecutionStatus {}
+pub enum AdminOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Admin<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Admin<'a> {
+  type Inner = Admin<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Admin<'a> {
+  pub const VT_COMMAND: flatbuffers::VOffsetT = 4;
+  pub const VT_USER_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_HAS_USER_ID: flatbuffers::VOffsetT = 8;
+  pub const VT_LEVEL: flatbuffers::VOffsetT = 10;
+  pub const VT_OPERATOR: flatbuffers::VOffsetT = 12;
+  pub const VT_REASON: flatbuffers::VOffsetT = 14;
+  pub const VT_TIMESTAMP: flatbuffers::VOffsetT = 16;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Admin { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args AdminArgs<'args>
+  ) -> flatbuffers::WIPOffset<Admin<'bldr>> {
+    let mut builder = AdminBuilder::new(_fbb);
+    builder.add_timestamp(args.timestamp);
+    builder.add_user_id(args.user_id);
+    if let Some(x) = args.reason { builder.add_reason(x); }
+    if let Some(x) = args.operator { builder.add_operator(x); }
+    builder.add_level(args.level);
+    builder.add_has_user_id(args.has_user_id);
+    builder.add_command(args.command);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn command(&self) -> AdminCommand {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<AdminCommand>(Admin::VT_COMMAND, Some(AdminCommand::Halt)).unwrap()}
+  }
+  #[inline]
+  pub fn user_id(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Admin::VT_USER_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn has_user_id(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(Admin::VT_HAS_USER_ID, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn level(&self) -> LogLevel {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<LogLevel>(Admin::VT_LEVEL, Some(LogLevel::Trace)).unwrap()}
+  }
+  #[inline]
+  pub fn operator(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Admin::VT_OPERATOR, None)}
+  }
+  #[inline]
+  pub fn reason(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(Admin::VT_REASON, None)}
+  }
+  #[inline]
+  pub fn timestamp(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(Admin::VT_TIMESTAMP, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Admin<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<AdminCommand>("command", Self::VT_COMMAND, false)?
+     .visit_field::<u64>("user_id", Self::VT_USER_ID, false)?
+     .visit_field::<bool>("has_user_id", Self::VT_HAS_USER_ID, false)?
+     .visit_field::<LogLevel>("level", Self::VT_LEVEL, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("operator", Self::VT_OPERATOR, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("reason", Self::VT_REASON, false)?
+     .visit_field::<u64>("timestamp", Self::VT_TIMESTAMP, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct AdminArgs<'a> {
+    pub command: AdminCommand,
+    pub user_id: u64,
+    pub has_user_id: bool,
+    pub level: LogLevel,
+    pub operator: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub reason: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub timestamp: u64,
+}
+impl<'a> Default for AdminArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    AdminArgs {
+      command: AdminCommand::Halt,
+      user_id: 0,
+      has_user_id: false,
+      level: LogLevel::Trace,
+      operator: None,
+      reason: None,
+      timestamp: 0,
+    }
+  }
+}
+
+pub struct AdminBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> AdminBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_command(&mut self, command: AdminCommand) {
+    self.fbb_.push_slot::<AdminCommand>(Admin::VT_COMMAND, command, AdminCommand::Halt);
+  }
+  #[inline]
+  pub fn add_user_id(&mut self, user_id: u64) {
+    self.fbb_.push_slot::<u64>(Admin::VT_USER_ID, user_id, 0);
+  }
+  #[inline]
+  pub fn add_has_user_id(&mut self, has_user_id: bool) {
+    self.fbb_.push_slot::<bool>(Admin::VT_HAS_USER_ID, has_user_id, false);
+  }
+  #[inline]
+  pub fn add_level(&mut self, level: LogLevel) {
+    self.fbb_.push_slot::<LogLevel>(Admin::VT_LEVEL, level, LogLevel::Trace);
+  }
+  #[inline]
+  pub fn add_operator(&mut self, operator: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Admin::VT_OPERATOR, operator);
+  }
+  #[inline]
+  pub fn add_reason(&mut self, reason: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Admin::VT_REASON, reason);
+  }
+  #[inline]
+  pub fn add_timestamp(&mut self, timestamp: u64) {
+    self.fbb_.push_slot::<u64>(Admin::VT_TIMESTAMP, timestamp, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> AdminBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    AdminBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Admin<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Admin<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Admin");
+      ds.field("command", &self.command());
+      ds.field("user_id", &self.user_id());
+      ds.field("has_user_id", &self.has_user_id());
+      ds.field("level", &self.level());
+      ds.field("operator", &self.operator());
+      ds.field("reason", &self.reason());
+      ds.field("timestamp", &self.timestamp());
+      ds.finish()
+  }
+}
+pub enum ExecutionReportOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ExecutionReport<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ExecutionReport<'a> {
+  type Inner = ExecutionReport<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ExecutionReport<'a> {
+  pub const VT_ORDER_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_CLIENT_ORDER_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_USER_ID: flatbuffers::VOffsetT = 8;
+  pub const VT_SIDE: flatbuffers::VOffsetT = 10;
+  pub const VT_ORDER_TYPE: flatbuffers::VOffsetT = 12;
+  pub const VT_PRICE: flatbuffers::VOffsetT = 14;
+  pub const VT_STATUS: flatbuffers::VOffsetT = 16;
+  pub const VT_REJECT_REASON: flatbuffers::VOffsetT = 18;
+  pub const VT_LAST_QUANTITY: flatbuffers::VOffsetT = 20;
+  pub const VT_LAST_PRICE: flatbuffers::VOffsetT = 22;
+  pub const VT_CUMULATIVE_QUANTITY: flatbuffers::VOffsetT = 24;
+  pub const VT_REMAINING_QUANTITY: flatbuffers::VOffsetT = 26;
+  pub const VT_TIMESTAMP: flatbuffers::VOffsetT = 28;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ExecutionReport { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ExecutionReportArgs<'args>
+  ) -> flatbuffers::WIPOffset<ExecutionReport<'bldr>> {
+    let mut builder = ExecutionReportBuilder::new(_fbb);
+    builder.add_timestamp(args.timestamp);
+    builder.add_remaining_quantity(args.remaining_quantity);
+    builder.add_cumulative_quantity(args.cumulative_quantity);
+    builder.add_last_price(args.last_price);
+    builder.add_last_quantity(args.last_quantity);
+    builder.add_price(args.price);
+    builder.add_user_id(args.user_id);
+    builder.add_order_id(args.order_id);
+    if let Some(x) = args.reject_reason { builder.add_reject_reason(x); }
+    if let Some(x) = args.client_order_id { builder.add_client_order_id(x); }
+    builder.add_status(args.status);
+    builder.add_order_type(args.order_type);
+    builder.add_side(args.side);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn order_id(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ExecutionReport::VT_ORDER_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn client_order_id(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ExecutionReport::VT_CLIENT_ORDER_ID, None)}
+  }
+  #[inline]
+  pub fn user_id(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ExecutionReport::VT_USER_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn side(&self) -> super::order::Side {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<super::order::Side>(ExecutionReport::VT_SIDE, Some(super::order::Side::Buy)).unwrap()}
+  }
+  #[inline]
+  pub fn order_type(&self) -> super::order::OrderType {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<super::order::OrderType>(ExecutionReport::VT_ORDER_TYPE, Some(super::order::OrderType::Limit)).unwrap()}
+  }
+  #[inline]
+  pub fn price(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ExecutionReport::VT_PRICE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn status(&self) -> ExecutionStatus {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<ExecutionStatus>(ExecutionReport::VT_STATUS, Some(ExecutionStatus::Accepted)).unwrap()}
+  }
+  #[inline]
+  pub fn reject_reason(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ExecutionReport::VT_REJECT_REASON, None)}
+  }
+  #[inline]
+  pub fn last_quantity(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ExecutionReport::VT_LAST_QUANTITY, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn last_price(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ExecutionReport::VT_LAST_PRICE, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn cumulative_quantity(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ExecutionReport::VT_CUMULATIVE_QUANTITY, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn remaining_quantity(&self) -> f64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<f64>(ExecutionReport::VT_REMAINING_QUANTITY, Some(0.0)).unwrap()}
+  }
+  #[inline]
+  pub fn timestamp(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(ExecutionReport::VT_TIMESTAMP, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ExecutionReport<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("order_id", Self::VT_ORDER_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("client_order_id", Self::VT_CLIENT_ORDER_ID, false)?
+     .visit_field::<u64>("user_id", Self::VT_USER_ID, false)?
+     .visit_field::<super::order::Side>("side", Self::VT_SIDE, false)?
+     .visit_field::<super::order::OrderType>("order_type", Self::VT_ORDER_TYPE, false)?
+     .visit_field::<f64>("price", Self::VT_PRICE, false)?
+     .visit_field::<ExecutionStatus>("status", Self::VT_STATUS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("reject_reason", Self::VT_REJECT_REASON, false)?
+     .visit_field::<f64>("last_quantity", Self::VT_LAST_QUANTITY, false)?
+     .visit_field::<f64>("last_price", Self::VT_LAST_PRICE, false)?
+     .visit_field::<f64>("cumulative_quantity", Self::VT_CUMULATIVE_QUANTITY, false)?
+     .visit_field::<f64>("remaining_quantity", Self::VT_REMAINING_QUANTITY, false)?
+     .visit_field::<u64>("timestamp", Self::VT_TIMESTAMP, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ExecutionReportArgs<'a> {
+    pub order_id: u64,
+    pub client_order_id: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub user_id: u64,
+    pub side: super::order::Side,
+    pub order_type: super::order::OrderType,
+    pub price: f64,
+    pub status: ExecutionStatus,
+    pub reject_reason: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub last_quantity: f64,
+    pub last_price: f64,
+    pub cumulative_quantity: f64,
+    pub remaining_quantity: f64,
+    pub timestamp: u64,
+}
+impl<'a> Default for ExecutionReportArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ExecutionReportArgs {
+      order_id: 0,
+      client_order_id: None,
+      user_id: 0,
+      side: super::order::Side::Buy,
+      order_type: super::order::OrderType::Limit,
+      price: 0.0,
+      status: ExecutionStatus::Accepted,
+      reject_reason: None,
+      last_quantity: 0.0,
+      last_price: 0.0,
+      cumulative_quantity: 0.0,
+      remaining_quantity: 0.0,
+      timestamp: 0,
+    }
+  }
+}
+
+pub struct ExecutionReportBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ExecutionReportBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_order_id(&mut self, order_id: u64) {
+    self.fbb_.push_slot::<u64>(ExecutionReport::VT_ORDER_ID, order_id, 0);
+  }
+  #[inline]
+  pub fn add_client_order_id(&mut self, client_order_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ExecutionReport::VT_CLIENT_ORDER_ID, client_order_id);
+  }
+  #[inline]
+  pub fn add_user_id(&mut self, user_id: u64) {
+    self.fbb_.push_slot::<u64>(ExecutionReport::VT_USER_ID, user_id, 0);
+  }
+  #[inline]
+  pub fn add_side(&mut self, side: super::order::Side) {
+    self.fbb_.push_slot::<super::order::Side>(ExecutionReport::VT_SIDE, side, super::order::Side::Buy);
+  }
+  #[inline]
+  pub fn add_order_type(&mut self, order_type: super::order::OrderType) {
+    self.fbb_.push_slot::<super::order::OrderType>(ExecutionReport::VT_ORDER_TYPE, order_type, super::order::OrderType::Limit);
+  }
+  #[inline]
+  pub fn add_price(&mut self, price: f64) {
+    self.fbb_.push_slot::<f64>(ExecutionReport::VT_PRICE, price, 0.0);
+  }
+  #[inline]
+  pub fn add_status(&mut self, status: ExecutionStatus) {
+    self.fbb_.push_slot::<ExecutionStatus>(ExecutionReport::VT_STATUS, status, ExecutionStatus::Accepted);
+  }
+  #[inline]
+  pub fn add_reject_reason(&mut self, reject_reason: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ExecutionReport::VT_REJECT_REASON, reject_reason);
+  }
+  #[inline]
+  pub fn add_last_quantity(&mut self, last_quantity: f64) {
+    self.fbb_.push_slot::<f64>(ExecutionReport::VT_LAST_QUANTITY, last_quantity, 0.0);
+  }
+  #[inline]
+  pub fn add_last_price(&mut self, last_price: f64) {
+    self.fbb_.push_slot::<f64>(ExecutionReport::VT_LAST_PRICE, last_price, 0.0);
+  }
+  #[inline]
+  pub fn add_cumulative_quantity(&mut self, cumulative_quantity: f64) {
+    self.fbb_.push_slot::<f64>(ExecutionReport::VT_CUMULATIVE_QUANTITY, cumulative_quantity, 0.0);
+  }
+  #[inline]
+  pub fn add_remaining_quantity(&mut self, remaining_quantity: f64) {
+    self.fbb_.push_slot::<f64>(ExecutionReport::VT_REMAINING_QUANTITY, remaining_quantity, 0.0);
+  }
+  #[inline]
+  pub fn add_timestamp(&mut self, timestamp: u64) {
+    self.fbb_.push_slot::<u64>(ExecutionReport::VT_TIMESTAMP, timestamp, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ExecutionReportBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ExecutionReportBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ExecutionReport<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ExecutionReport<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ExecutionReport");
+      ds.field("order_id", &self.order_id());
+      ds.field("client_order_id", &self.client_order_id());
+      ds.field("user_id", &self.user_id());
+      ds.field("side", &self.side());
+      ds.field("order_type", &self.order_type());
+      ds.field("price", &self.price());
+      ds.field("status", &self.status());
+      ds.field("reject_reason", &self.reject_reason());
+      ds.field("last_quantity", &self.last_quantity());
+      ds.field("last_price", &self.last_price());
+      ds.field("cumulative_quantity", &self.cumulative_quantity());
+      ds.field("remaining_quantity", &self.remaining_quantity());
+      ds.field("timestamp", &self.timestamp());
+      ds.finish()
+  }
+}
 pub enum SpotLogOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
@@ -128,6 +943,8 @@ impl<'a> SpotLog<'a> {
   pub const VT_SEQ_ID: flatbuffers::VOffsetT = 6;
   pub const VT_ORDER: flatbuffers::VOffsetT = 8;
   pub const VT_TRADE: flatbuffers::VOffsetT = 10;
+  pub const VT_ADMIN: flatbuffers::VOffsetT = 12;
+  pub const VT_REPORT: flatbuffers::VOffsetT = 14;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -140,6 +957,8 @@ impl<'a> SpotLog<'a> {
   ) -> flatbuffers::WIPOffset<SpotLog<'bldr>> {
     let mut builder = SpotLogBuilder::new(_fbb);
     builder.add_seq_id(args.seq_id);
+    if let Some(x) = args.report { builder.add_report(x); }
+    if let Some(x) = args.admin { builder.add_admin(x); }
     if let Some(x) = args.trade { builder.add_trade(x); }
     if let Some(x) = args.order { builder.add_order(x); }
     builder.add_log_type(args.log_type);
@@ -175,6 +994,20 @@ impl<'a> SpotLog<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<super::trade::Trade>>(SpotLog::VT_TRADE, None)}
   }
+  #[inline]
+  pub fn admin(&self) -> Option<Admin<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Admin>>(SpotLog::VT_ADMIN, None)}
+  }
+  #[inline]
+  pub fn report(&self) -> Option<ExecutionReport<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<ExecutionReport>>(SpotLog::VT_REPORT, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for SpotLog<'_> {
@@ -188,6 +1021,8 @@ impl flatbuffers::Verifiable for SpotLog<'_> {
      .visit_field::<u64>("seq_id", Self::VT_SEQ_ID, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<super::order::Order>>("order", Self::VT_ORDER, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<super::trade::Trade>>("trade", Self::VT_TRADE, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Admin>>("admin", Self::VT_ADMIN, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<ExecutionReport>>("report", Self::VT_REPORT, false)?
      .finish();
     Ok(())
   }
@@ -197,6 +1032,8 @@ pub struct SpotLogArgs<'a> {
     pub seq_id: u64,
     pub order: Option<flatbuffers::WIPOffset<super::order::Order<'a>>>,
     pub trade: Option<flatbuffers::WIPOffset<super::trade::Trade<'a>>>,
+    pub admin: Option<flatbuffers::WIPOffset<Admin<'a>>>,
+    pub report: Option<flatbuffers::WIPOffset<ExecutionReport<'a>>>,
 }
 impl<'a> Default for SpotLogArgs<'a> {
   #[inline]
@@ -206,6 +1043,8 @@ impl<'a> Default for SpotLogArgs<'a> {
       seq_id: 0,
       order: None,
       trade: None,
+      admin: None,
+      report: None,
     }
   }
 }
@@ -232,6 +1071,14 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SpotLogBuilder<'a, 'b, A> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<super::trade::Trade>>(SpotLog::VT_TRADE, trade);
   }
   #[inline]
+  pub fn add_admin(&mut self, admin: flatbuffers::WIPOffset<Admin<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Admin>>(SpotLog::VT_ADMIN, admin);
+  }
+  #[inline]
+  pub fn add_report(&mut self, report: flatbuffers::WIPOffset<ExecutionReport<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<ExecutionReport>>(SpotLog::VT_REPORT, report);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> SpotLogBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     SpotLogBuilder {
@@ -253,6 +1100,8 @@ impl core::fmt::Debug for SpotLog<'_> {
       ds.field("seq_id", &self.seq_id());
       ds.field("order", &self.order());
       ds.field("trade", &self.trade());
+      ds.field("admin", &self.admin());
+      ds.field("report", &self.report());
       ds.finish()
   }
 }
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicU8, Ordering};
+use std::sync::{Arc, RwLock};
+use serde::Serialize;
+use crate::date::current_timestamp;
+use crate::supervisor::{EngineStatus, EngineStatuses};
+
+// 引擎启动阶段
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EnginePhase {
+    //从快照恢复订单簿
+    Restoring,
+    //重放快照之后、启动时已存在的输入消息
+    Replaying,
+    //已追上输入主题，正常消费
+    Consuming,
+}
+
+impl fmt::Display for EnginePhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let phase_str = match self {
+            EnginePhase::Restoring => "Restoring",
+            EnginePhase::Replaying => "Replaying",
+            EnginePhase::Consuming => "Consuming",
+        };
+        write!(f, "{}", phase_str)
+    }
+}
+
+/// 单个引擎的健康状态，由引擎写入，探针读取
+#[derive(Debug)]
+pub struct EngineHealth {
+    phase: AtomicU8,
+    heartbeat: AtomicU64,  // 撮合循环最近一次运转的时间，毫秒级时间戳
+}
+
+impl Default for EngineHealth {
+    fn default() -> Self {
+        EngineHealth {
+            phase: AtomicU8::new(EnginePhase::Restoring as u8),
+            heartbeat: AtomicU64::new(current_timestamp()),
+        }
+    }
+}
+
+impl EngineHealth {
+    pub fn set_phase(&self, phase: EnginePhase) {
+        self.phase.store(phase as u8, Ordering::Relaxed);
+    }
+
+    pub fn phase(&self) -> EnginePhase {
+        match self.phase.load(Ordering::Relaxed) {
+            0 => EnginePhase::Restoring,
+            1 => EnginePhase::Replaying,
+            _ => EnginePhase::Consuming,
+        }
+    }
+
+    /// 撮合循环每次运转时调用
+    pub fn beat(&self) {
+        self.heartbeat.store(current_timestamp(), Ordering::Relaxed);
+    }
+
+    pub fn heartbeat(&self) -> u64 {
+        self.heartbeat.load(Ordering::Relaxed)
+    }
+}
+
+// 单个交易对的检查结果
+#[derive(Debug, Serialize)]
+pub struct EngineReport {
+    pub symbol: String,
+    pub status: EngineStatus,
+    pub phase: Option<EnginePhase>,
+    pub heartbeat_age_ms: Option<u64>,
+    pub ready: bool,
+    pub live: bool,
+}
+
+/// 探针检查结果
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub ready: bool,
+    pub live: bool,
+    pub symbols_loaded: bool,
+    pub engines: Vec<EngineReport>,
+}
+
+/// 各交易对引擎的健康状态。引擎每次启动都登记新的状态，交易对停止撮合后移除
+#[derive(Debug, Default)]
+pub struct HealthRegistry {
+    engines: RwLock<HashMap<String, Arc<EngineHealth>>>,
+    symbols_loaded: AtomicBool,
+}
+
+impl HealthRegistry {
+    pub fn new() -> Self {
+        HealthRegistry::default()
+    }
+
+    /// 为即将启动的引擎登记新的健康状态
+    pub fn register(&self, symbol: &str) -> Arc<EngineHealth> {
+        let health = Arc::new(EngineHealth::default());
+        self.engines.write().expect("engine health poisoned").insert(symbol.to_string(), health.clone());
+        health
+    }
+
+    pub fn remove(&self, symbol: &str) {
+        self.engines.write().expect("engine health poisoned").remove(symbol);
+    }
+
+    /// 首次成功读取交易对配置后调用，此前不认为就绪
+    pub fn set_symbols_loaded(&self) {
+        self.symbols_loaded.store(true, Ordering::Relaxed);
+    }
+
+    /// 检查全部引擎：每个运行中或热备的引擎都已追上输入主题时就绪，等待租约的引擎不影响就绪，
+    /// 重启中或已失败的引擎视为未就绪；任一撮合循环超过 `stall_threshold_ms` 没有运转时存活检查失败。
+    /// 恢复快照期间撮合循环尚未启动，不计入存活检查
+    pub fn check(&self, statuses: &EngineStatuses, now: u64, stall_threshold_ms: u64) -> HealthReport {
+        let statuses = statuses.read().expect("engine statuses poisoned");
+        let engines = self.engines.read().expect("engine health poisoned");
+
+        let mut reports: Vec<EngineReport> = statuses.iter()
+            .map(|(symbol, status)| {
+                let health = engines.get(symbol);
+                let phase = health.map(|health| health.phase());
+                let heartbeat_age_ms = health.map(|health| now.saturating_sub(health.heartbeat()));
+                let running = matches!(status, EngineStatus::Running | EngineStatus::Standby);
+                let ready = match status {
+                    EngineStatus::WaitingLease => true,
+                    EngineStatus::Running | EngineStatus::Standby => phase == Some(EnginePhase::Consuming),
+                    EngineStatus::Restarting | EngineStatus::Failed => false,
+                };
+                let live = !running
+                    || phase == Some(EnginePhase::Restoring)
+                    || heartbeat_age_ms.is_none_or(|age| age <= stall_threshold_ms);
+                EngineReport { symbol: symbol.clone(), status: *status, phase, heartbeat_age_ms, ready, live }
+            })
+            .collect();
+        reports.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+
+        let symbols_loaded = self.symbols_loaded.load(Ordering::Relaxed);
+        HealthReport {
+            ready: symbols_loaded && reports.iter().all(|report| report.ready),
+            live: reports.iter().all(|report| report.live),
+            symbols_loaded,
+            engines: reports,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_health_check() {
+        let registry = HealthRegistry::new();
+        let statuses: EngineStatuses = Arc::new(RwLock::new(HashMap::from([
+            ("BTC/USDT".to_string(), EngineStatus::Running),
+            ("ETH/USDT".to_string(), EngineStatus::WaitingLease),
+        ])));
+        let health = registry.register("BTC/USDT");
+        let now = health.heartbeat();
+
+        // 交易对配置尚未读取，恢复快照期间不就绪但存活
+        let report = registry.check(&statuses, now + 60_000, 30_000);
+        assert!(!report.ready && report.live);
+
+        registry.set_symbols_loaded();
+        health.set_phase(EnginePhase::Replaying);
+        let report = registry.check(&statuses, now, 30_000);
+        assert!(!report.ready && report.live);
+
+        health.set_phase(EnginePhase::Consuming);
+        assert!(registry.check(&statuses, now, 30_000).ready);
+
+        // 撮合循环停滞
+        let report = registry.check(&statuses, now + 60_000, 30_000);
+        assert!(report.ready && !report.live);
+    }
+}
@@ -0,0 +1,252 @@
+use std::convert::Infallible;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use rdkafka::producer::FutureProducer;
+use serde::{Deserialize, Serialize};
+use tklog::{async_error, async_info};
+use tokio::sync::watch;
+use warp::http::StatusCode;
+use warp::reply::{self, Reply};
+use warp::Filter;
+
+use crate::admin::{self, AdminState};
+use crate::config::Config;
+use crate::date::current_timestamp;
+use crate::health::{HealthRegistry, HealthReport};
+use crate::market_data::MarketDataStore;
+use crate::metrics;
+use crate::supervisor::{EngineStatus, EngineStatuses};
+use crate::ws;
+
+const DEFAULT_DEPTH_LIMIT: usize = 20;
+const DEFAULT_TRADES_LIMIT: usize = 50;
+
+// 查询接口共享的状态，所有数据都来自引擎写入的视图，查询不经过订单簿锁
+#[derive(Clone)]
+pub struct ApiState {
+    pub statuses: EngineStatuses,
+    pub market_data: MarketDataStore,
+    pub admin_token: Option<String>,        // 管理接口令牌，未配置时不开放管理接口
+    pub producer: Option<FutureProducer>,   // 管理指令写入输入主题使用
+    pub health: Arc<HealthRegistry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LimitQuery {
+    limit: Option<usize>,
+}
+
+#[derive(Debug, Serialize)]
+struct SymbolStatus {
+    symbol: String,
+    status: EngineStatus,
+    halted: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorBody {
+    error: String,
+}
+
+// 路径中的交易对以 '_' 分隔，例如 BTC_USDT -> BTC/USDT
+fn parse_symbol(symbol: &str) -> String {
+    symbol.replace('_', "/")
+}
+
+fn not_found(message: String) -> reply::Response {
+    reply::with_status(reply::json(&ErrorBody { error: message }), StatusCode::NOT_FOUND).into_response()
+}
+
+// 探针结果，检查通过时返回 200，否则返回 503
+fn probe(report: HealthReport, ok: bool) -> reply::Response {
+    let status = if ok { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    reply::with_status(reply::json(&report), status).into_response()
+}
+
+fn health_report(state: &ApiState) -> HealthReport {
+    let stall_threshold_ms = Config::global().engine_config.stall_threshold_ms;
+    state.health.check(&state.statuses, current_timestamp(), stall_threshold_ms)
+}
+
+fn with_state(state: ApiState) -> impl Filter<Extract = (ApiState,), Error = Infallible> + Clone {
+    warp::any().map(move || state.clone())
+}
+
+/// 查询接口的全部路由，包括 WebSocket 推送、Prometheus 指标、健康探针和管理接口
+pub fn routes(state: ApiState) -> impl Filter<Extract = (reply::Response,), Error = warp::Rejection> + Clone {
+    let symbols = warp::path!("symbols")
+        .and(with_state(state.clone()))
+        .map(|state: ApiState| {
+            let mut symbols: Vec<SymbolStatus> = state.statuses.read().expect("engine statuses poisoned")
+                .iter()
+                .map(|(symbol, status)| SymbolStatus {
+                    symbol: symbol.clone(),
+                    status: *status,
+                    halted: state.market_data.read(symbol, |market| market.halted()).unwrap_or(false),
+                })
+                .collect();
+            symbols.sort_by(|a, b| a.symbol.cmp(&b.symbol));
+            reply::json(&symbols).into_response()
+        });
+
+    let depth = warp::path!("depth" / String)
+        .and(warp::query::<LimitQuery>())
+        .and(with_state(state.clone()))
+        .map(|symbol: String, query: LimitQuery, state: ApiState| {
+            let symbol = parse_symbol(&symbol);
+            let limit = query.limit.unwrap_or(DEFAULT_DEPTH_LIMIT);
+            match state.market_data.read(&symbol, |market| market.depth(limit)) {
+                Some(depth) => reply::json(&depth).into_response(),
+                None => not_found(format!("unknown symbol {}", symbol)),
+            }
+        });
+
+    let ticker = warp::path!("ticker" / String)
+        .and(with_state(state.clone()))
+        .map(|symbol: String, state: ApiState| {
+            let symbol = parse_symbol(&symbol);
+            match state.market_data.read(&symbol, |market| market.ticker()) {
+                Some(Some(ticker)) => reply::json(&ticker).into_response(),
+                Some(None) => not_found(format!("no ticker for {} yet", symbol)),
+                None => not_found(format!("unknown symbol {}", symbol)),
+            }
+        });
+
+    let trades = warp::path!("trades" / String)
+        .and(warp::query::<LimitQuery>())
+        .and(with_state(state.clone()))
+        .map(|symbol: String, query: LimitQuery, state: ApiState| {
+            let symbol = parse_symbol(&symbol);
+            let limit = query.limit.unwrap_or(DEFAULT_TRADES_LIMIT);
+            match state.market_data.read(&symbol, |market| market.trades(limit)) {
+                Some(trades) => reply::json(&trades).into_response(),
+                None => not_found(format!("unknown symbol {}", symbol)),
+            }
+        });
+
+    let order = warp::path!("order" / String / u64)
+        .and(with_state(state.clone()))
+        .map(|symbol: String, order_id: u64, state: ApiState| {
+            let symbol = parse_symbol(&symbol);
+            match state.market_data.read(&symbol, |market| market.order(order_id)) {
+                Some(Some(order)) => reply::json(&order).into_response(),
+                Some(None) => not_found(format!("order {} is not resting in {}", order_id, symbol)),
+                None => not_found(format!("unknown symbol {}", symbol)),
+            }
+        });
+
+    let metrics = warp::path!("metrics").map(|| {
+        reply::with_header(metrics::render(), "content-type", "text/plain; version=0.0.4").into_response()
+    });
+
+    let healthz = warp::path!("healthz")
+        .and(with_state(state.clone()))
+        .map(|state: ApiState| {
+            let report = health_report(&state);
+            let live = report.live;
+            probe(report, live)
+        });
+
+    let readyz = warp::path!("readyz")
+        .and(with_state(state.clone()))
+        .map(|state: ApiState| {
+            let report = health_report(&state);
+            let ready = report.ready && report.live;
+            probe(report, ready)
+        });
+
+    let stream = ws::route(state.market_data);
+    let admin = admin::routes(AdminState {
+        statuses: state.statuses,
+        token: state.admin_token,
+        producer: state.producer,
+    });
+
+    warp::get()
+        .and(
+            symbols.or(depth).unify()
+                .or(ticker).unify()
+                .or(trades).unify()
+                .or(order).unify()
+                .or(metrics).unify()
+                .or(healthz).unify()
+                .or(readyz).unify()
+                .or(stream).unify()
+        )
+        .or(admin).unify()
+}
+
+/// 按 http_config 启动查询接口，`shutdown` 收到关闭信号后停止
+pub async fn serve(state: ApiState, mut shutdown: watch::Receiver<bool>) {
+    let bind_address = Config::global().http_config.bind_address.clone();
+    let addr: SocketAddr = match bind_address.parse() {
+        Ok(addr) => addr,
+        Err(e) => {
+            async_error!(format!("Invalid http bind address {}: {}", bind_address, e));
+            return;
+        }
+    };
+
+    let signal = async move {
+        while !*shutdown.borrow() {
+            if shutdown.changed().await.is_err() {
+                break;
+            }
+        }
+    };
+
+    match warp::serve(routes(state)).try_bind_with_graceful_shutdown(addr, signal) {
+        Ok((addr, server)) => {
+            async_info!("HTTP API listening on ", addr);
+            server.await;
+        }
+        Err(e) => async_error!(format!("Failed to bind http api on {}: {}", addr, e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::RwLock;
+    use crate::order::{Order, OrderType, Side};
+    use crate::order_book::OrderBook;
+
+    #[tokio::test]
+    async fn test_query_routes() {
+        let mut order_book = OrderBook::new();
+        let order = Order::new(7, 100.0, 2.0, OrderType::Limit, Side::Buy);
+        order_book.add_order(order.clone());
+
+        let market_data = MarketDataStore::new();
+        market_data.symbol("BTC/USDT").write().unwrap().reset(&order_book, 10, 0);
+        let statuses = Arc::new(RwLock::new(HashMap::from([("BTC/USDT".to_string(), EngineStatus::Running)])));
+        let routes = routes(ApiState { statuses, market_data, admin_token: Some("secret".to_string()), producer: None, health: Arc::new(HealthRegistry::new()) });
+
+        let response = warp::test::request().path("/depth/BTC_USDT?limit=5").reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+        let depth: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(depth["bids"][0]["quantity"], 2.0);
+
+        let response = warp::test::request().path(&format!("/order/BTC_USDT/{}", order.id)).reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = warp::test::request().path("/order/BTC_USDT/0").reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+        let response = warp::test::request().path("/trades/ETH_USDT").reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let response = warp::test::request().path("/symbols").reply(&routes).await;
+        let symbols: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(symbols[0]["status"], "Running");
+        assert_eq!(symbols[0]["halted"], false);
+
+        let halt = warp::test::request().method("POST").path("/admin/BTC_USDT/halt").json(&serde_json::json!({"operator": "ops"}));
+        let response = halt.reply(&routes).await;
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        let halt = warp::test::request().method("POST").path("/admin/BTC_USDT/halt")
+            .header("authorization", "Bearer secret")
+            .json(&serde_json::json!({"operator": "ops"}));
+        assert_eq!(halt.reply(&routes).await.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+}
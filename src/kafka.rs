@@ -1,11 +1,13 @@
+use std::collections::HashSet;
 use std::time::Duration;
 
 use futures::future;
 
 use rdkafka::config::{ClientConfig, RDKafkaLogLevel};
 use rdkafka::consumer::stream_consumer::StreamConsumer;
-use rdkafka::consumer::{Consumer, DefaultConsumerContext};
-use rdkafka::error::{KafkaError};
+use rdkafka::consumer::{BaseConsumer, Consumer, DefaultConsumerContext};
+use rdkafka::error::{KafkaError, RDKafkaErrorCode};
+use rdkafka::{Offset, TopicPartitionList};
 use rdkafka::producer::{FutureProducer, FutureRecord};
 use rdkafka::util::get_rdkafka_version;
 // 修改这里：使用新的Message引入路径
@@ -66,6 +68,60 @@ pub fn create_producer(brokers: &str) -> Option<FutureProducer> {
     }
 }
 
+/// 查询主题的全部分区号，主题不存在时返回错误。
+/// 阻塞调用，异步上下文中需放在 spawn_blocking 中执行
+pub fn fetch_partitions<C: Consumer>(consumer: &C, topic: &str, timeout: Duration) -> Result<Vec<i32>, KafkaError> {
+    let metadata = consumer.fetch_metadata(Some(topic), timeout)?;
+    let mut partitions = Vec::new();
+    for metadata_topic in metadata.topics().iter().filter(|metadata_topic| metadata_topic.name() == topic) {
+        if let Some(e) = metadata_topic.error() {
+            return Err(KafkaError::MetadataFetch(e.into()));
+        }
+        partitions.extend(metadata_topic.partitions().iter().map(|partition| partition.id()));
+    }
+    Ok(partitions)
+}
+
+/// 读取主题每个分区的最后一条消息，空分区跳过，主题为空时返回空列表。
+/// 同一主题的消息可能按 key 分布在任一分区，调用方需在全部分区中取最新的一条。
+/// 阻塞调用，异步上下文中需放在 spawn_blocking 中执行
+pub fn fetch_last_payloads(brokers: &str, topic: &str, timeout: Duration) -> Result<Vec<Vec<u8>>, KafkaError> {
+    let consumer: BaseConsumer = ClientConfig::new()
+        .set("bootstrap.servers", brokers)
+        .set("group.id", format!("{}_tail", topic))
+        .set("enable.auto.commit", "false")
+        .create()?;
+
+    let mut assignment = TopicPartitionList::new();
+    let mut remaining = HashSet::new();
+    for partition in fetch_partitions(&consumer, topic, timeout)? {
+        let (low, high) = consumer.fetch_watermarks(topic, partition, timeout)?;
+        if high > low {
+            assignment.add_partition_offset(topic, partition, Offset::Offset(high - 1))?;
+            remaining.insert(partition);
+        }
+    }
+    if remaining.is_empty() {
+        return Ok(Vec::new());
+    }
+    consumer.assign(&assignment)?;
+
+    // 每个分区只取定位到的第一条消息，其后新写入的消息忽略
+    let mut payloads = Vec::with_capacity(remaining.len());
+    while !remaining.is_empty() {
+        match consumer.poll(timeout) {
+            Some(Ok(message)) => {
+                if remaining.remove(&message.partition()) {
+                    payloads.extend(message.payload().map(|p| p.to_vec()));
+                }
+            }
+            Some(Err(e)) => return Err(e),
+            None => return Err(KafkaError::MessageConsumption(RDKafkaErrorCode::OperationTimedOut)),
+        }
+    }
+    Ok(payloads)
+}
+
 #[allow(dead_code)]
 async fn process_messages(
     brokers: &str,
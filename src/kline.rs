@@ -0,0 +1,296 @@
+use std::fmt;
+use serde::{Deserialize, Serialize};
+use tklog::async_error;
+use tokio::sync::mpsc;
+use crate::db_pool::get_database;
+use crate::model::spot_kline::SpotKline;
+
+// K 线周期
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum KlineInterval {
+    #[serde(rename = "1m")]
+    M1,
+    #[serde(rename = "5m")]
+    M5,
+    #[serde(rename = "15m")]
+    M15,
+    #[serde(rename = "1h")]
+    H1,
+    #[serde(rename = "4h")]
+    H4,
+    #[serde(rename = "1d")]
+    D1,
+}
+
+impl KlineInterval {
+    pub const ALL: [KlineInterval; 6] = [
+        KlineInterval::M1,
+        KlineInterval::M5,
+        KlineInterval::M15,
+        KlineInterval::H1,
+        KlineInterval::H4,
+        KlineInterval::D1,
+    ];
+
+    /// 周期长度（毫秒）
+    pub fn millis(&self) -> u64 {
+        const MINUTE: u64 = 60 * 1000;
+        match self {
+            KlineInterval::M1 => MINUTE,
+            KlineInterval::M5 => 5 * MINUTE,
+            KlineInterval::M15 => 15 * MINUTE,
+            KlineInterval::H1 => 60 * MINUTE,
+            KlineInterval::H4 => 4 * 60 * MINUTE,
+            KlineInterval::D1 => 24 * 60 * MINUTE,
+        }
+    }
+
+    /// 时间戳所在周期的开始时间，周期按 UTC 零点对齐
+    pub fn open_time(&self, timestamp: u64) -> u64 {
+        timestamp - timestamp % self.millis()
+    }
+}
+
+impl fmt::Display for KlineInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let interval_str = match self {
+            KlineInterval::M1 => "1m",
+            KlineInterval::M5 => "5m",
+            KlineInterval::M15 => "15m",
+            KlineInterval::H1 => "1h",
+            KlineInterval::H4 => "4h",
+            KlineInterval::D1 => "1d",
+        };
+        write!(f, "{}", interval_str)
+    }
+}
+
+// K 线
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Kline {
+    pub symbol: String,
+    pub interval: KlineInterval,
+    pub open_time: u64,     // 毫秒级时间戳，含
+    pub close_time: u64,    // 毫秒级时间戳，不含
+    pub open: f64,
+    pub high: f64,
+    pub low: f64,
+    pub close: f64,
+    pub volume: f64,        // 成交量（基础币）
+    pub quote_volume: f64,  // 成交额（计价币）
+    pub trade_count: u64,
+    pub closed: bool,       // 是否已收盘，未收盘的 K 线仍会更新
+}
+
+impl Kline {
+    fn open(symbol: &str, interval: KlineInterval, price: f64, quantity: f64, timestamp: u64) -> Self {
+        let open_time = interval.open_time(timestamp);
+        Kline {
+            symbol: symbol.to_string(),
+            interval,
+            open_time,
+            close_time: open_time + interval.millis(),
+            open: price,
+            high: price,
+            low: price,
+            close: price,
+            volume: quantity,
+            quote_volume: price * quantity,
+            trade_count: 1,
+            closed: false,
+        }
+    }
+
+    fn add(&mut self, price: f64, quantity: f64) {
+        self.high = self.high.max(price);
+        self.low = self.low.min(price);
+        self.close = price;
+        self.volume += quantity;
+        self.quote_volume += price * quantity;
+        self.trade_count += 1;
+    }
+}
+
+// 单个周期正在进行中的 K 线
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct KlineSlot {
+    current: Option<Kline>,
+    updated: bool,  // 上次取出更新后是否有新成交
+}
+
+/// 按成交逐笔聚合各周期 K 线。K 线只按事件时间收盘：事件时钟取已处理输入消息时间戳的最大值，只增不减，
+/// 越过收盘时间时收盘，不受本机时钟和输入延迟的影响；晚到的旧时间戳成交计入当前 K 线，已收盘的周期不会重新开盘。
+/// 没有成交的周期不生成 K 线。可随快照持久化，从快照重放得到的 K 线与原先相同
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct KlineAggregator {
+    slots: Vec<(KlineInterval, KlineSlot)>,
+    #[serde(default)]
+    clock: u64,  // 事件时钟，毫秒级时间戳
+}
+
+impl Default for KlineAggregator {
+    fn default() -> Self {
+        KlineAggregator {
+            slots: KlineInterval::ALL.iter()
+                .map(|interval| (*interval, KlineSlot { current: None, updated: false }))
+                .collect(),
+            clock: 0,
+        }
+    }
+}
+
+impl KlineAggregator {
+    pub fn new() -> Self {
+        KlineAggregator::default()
+    }
+
+    /// 记录一笔成交，返回因此收盘的 K 线。成交计入事件时钟所在的周期
+    pub fn add_trade(&mut self, symbol: &str, price: f64, quantity: f64, timestamp: u64) -> Vec<Kline> {
+        let closed = self.advance(timestamp);
+        let timestamp = self.clock;
+        for (interval, slot) in &mut self.slots {
+            match &mut slot.current {
+                Some(kline) => kline.add(price, quantity),
+                current => *current = Some(Kline::open(symbol, *interval, price, quantity, timestamp)),
+            }
+            slot.updated = true;
+        }
+        closed
+    }
+
+    /// 用输入消息的时间戳推进事件时钟，返回收盘时间不晚于时钟的 K 线
+    pub fn advance(&mut self, timestamp: u64) -> Vec<Kline> {
+        self.clock = self.clock.max(timestamp);
+        let mut closed = Vec::new();
+        for (_, slot) in &mut self.slots {
+            if slot.current.as_ref().is_some_and(|kline| kline.close_time <= self.clock) {
+                let mut kline = slot.current.take().expect("checked above");
+                kline.closed = true;
+                closed.push(kline);
+                slot.updated = false;
+            }
+        }
+        closed
+    }
+
+    /// 取出上次调用以来有新成交的未收盘 K 线
+    pub fn take_updates(&mut self) -> Vec<Kline> {
+        let mut updates = Vec::new();
+        for (_, slot) in &mut self.slots {
+            if slot.updated {
+                slot.updated = false;
+                updates.extend(slot.current.clone());
+            }
+        }
+        updates
+    }
+
+    /// 各周期正在进行中的 K 线
+    pub fn current(&self) -> Vec<Kline> {
+        self.slots.iter().filter_map(|(_, slot)| slot.current.clone()).collect()
+    }
+}
+
+/// 已收盘 K 线的异步写入器，写入在独立任务中进行，不阻塞撮合
+#[derive(Debug, Clone)]
+pub struct KlineWriter {
+    sender: mpsc::Sender<Kline>,
+}
+
+impl KlineWriter {
+    pub fn spawn(capacity: usize) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<Kline>(capacity);
+        tokio::spawn(async move {
+            while let Some(kline) = receiver.recv().await {
+                let record = SpotKline::from(&kline);
+                // 数据库短暂不可用时重试几次，仍失败则放弃，K 线仍可从行情主题恢复
+                for attempt in 1..=3 {
+                    let upserted = match get_database() {
+                        Ok(db) => record.upsert(&db).await,
+                        Err(e) => Err(e),
+                    };
+                    match upserted {
+                        Ok(()) => break,
+                        Err(e) => {
+                            async_error!(format!(
+                                "Failed to persist {} {} kline at {} (attempt {}): {}",
+                                kline.symbol, kline.interval, kline.open_time, attempt, e
+                            ));
+                            tokio::time::sleep(std::time::Duration::from_millis(500 * attempt)).await;
+                        }
+                    }
+                }
+            }
+        });
+        KlineWriter { sender }
+    }
+
+    /// 放入写入队列，队列已满时丢弃并记录错误
+    pub async fn write(&self, kline: Kline) {
+        if let Err(e) = self.sender.try_send(kline) {
+            async_error!(format!("Failed to queue kline for persistence: {}", e));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_kline_aggregation() {
+        let mut klines = KlineAggregator::new();
+        let start = 1_700_000_160_000; // 某分钟开始的时间，其后两分钟内不跨越更长的周期
+        assert_eq!(start % 60_000, 0);
+
+        assert!(klines.add_trade("BTC/USDT", 100.0, 1.0, start).is_empty());
+        assert!(klines.add_trade("BTC/USDT", 110.0, 1.0, start + 10_000).is_empty());
+        assert!(klines.add_trade("BTC/USDT", 90.0, 2.0, start + 20_000).is_empty());
+        assert_eq!(klines.take_updates().len(), KlineInterval::ALL.len());
+        assert!(klines.take_updates().is_empty());
+
+        // 下一分钟的成交使 1m K 线收盘
+        let closed = klines.add_trade("BTC/USDT", 95.0, 1.0, start + 60_000);
+        assert_eq!(closed.len(), 1);
+        let kline = &closed[0];
+        assert_eq!(kline.interval, KlineInterval::M1);
+        assert!(kline.closed);
+        assert_eq!((kline.open, kline.high, kline.low, kline.close), (100.0, 110.0, 90.0, 90.0));
+        assert_eq!(kline.volume, 4.0);
+        assert_eq!(kline.trade_count, 3);
+
+        // 事件时钟越过收盘时间，没有新成交也收盘
+        let closed = klines.advance(start + 120_000);
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].open_time, start + 60_000);
+        assert_eq!(klines.current().len(), KlineInterval::ALL.len() - 1);
+    }
+
+    #[test]
+    fn test_kline_replay_across_boundary() {
+        let start = 1_700_000_160_000;
+        let mut klines = KlineAggregator::new();
+        klines.add_trade("BTC/USDT", 100.0, 1.0, start);
+        klines.add_trade("BTC/USDT", 110.0, 1.0, start + 10_000);
+        let snapshot = serde_json::to_string(&klines).unwrap();
+
+        // 下一分钟的消息使 1m K 线收盘，之后时间戳更早的成交计入当前周期，不会重新打开已收盘的 K 线
+        let replay = |klines: &mut KlineAggregator| {
+            let mut closed = klines.advance(start + 60_000);
+            closed.extend(klines.add_trade("BTC/USDT", 90.0, 2.0, start + 30_000));
+            closed.extend(klines.advance(start + 45_000));
+            closed.extend(klines.add_trade("BTC/USDT", 95.0, 1.0, start + 120_000));
+            closed
+        };
+        let closed = replay(&mut klines);
+        let minutes: Vec<_> = closed.iter().filter(|kline| kline.interval == KlineInterval::M1).collect();
+        assert_eq!(minutes.iter().map(|kline| kline.open_time).collect::<Vec<_>>(), vec![start, start + 60_000]);
+        assert_eq!((minutes[0].close, minutes[0].trade_count), (110.0, 2));
+        assert_eq!((minutes[1].low, minutes[1].trade_count), (90.0, 1));
+
+        // 从快照重放同样的输入得到相同的 K 线，覆盖写入不改变已持久化的结果
+        let mut restored: KlineAggregator = serde_json::from_str(&snapshot).unwrap();
+        assert_eq!(replay(&mut restored), closed);
+        assert_eq!(restored, klines);
+    }
+}
@@ -0,0 +1,118 @@
+use std::time::Duration;
+use anyhow::{anyhow, Result};
+use tklog::{async_error, async_info};
+use tokio::sync::watch;
+use tokio::time::Instant;
+
+use crate::config::Config;
+use crate::date::current_timestamp;
+use crate::db_pool::get_database;
+use crate::model::symbol_lease::SymbolLease;
+use crate::node::node_id;
+
+/// 交易对租约的获取、续约与释放
+#[derive(Debug, Clone)]
+pub struct LeaseKeeper {
+    symbol: String,
+    node_id: String,
+    ttl: Duration,
+    renew_interval: Duration,
+}
+
+impl LeaseKeeper {
+    /// 未开启 node_config.lease_enabled 时返回 None；开启但没有节点标识时返回配置错误
+    pub fn from_config(symbol: &str) -> Result<Option<Self>> {
+        let config = Config::global();
+        if !config.node_config.lease_enabled {
+            return Ok(None);
+        }
+
+        let node_id = node_id()
+            .ok_or_else(|| anyhow!("node_config.lease_enabled requires a node id (--node-id, SPOT_MATCH_NODE_ID or node_config.node_id)"))?;
+        Ok(Some(LeaseKeeper {
+            symbol: symbol.to_string(),
+            node_id,
+            ttl: Duration::from_millis(config.node_config.lease_ttl_ms),
+            renew_interval: Duration::from_millis(config.node_config.lease_renew_interval_ms),
+        }))
+    }
+
+    async fn renew(&self) -> Result<bool, sqlx::Error> {
+        SymbolLease::try_acquire(
+            &get_database()?,
+            &self.symbol,
+            &self.node_id,
+            current_timestamp() as i64,
+            self.ttl.as_millis() as i64,
+        ).await
+    }
+
+    async fn try_acquire(&self) -> bool {
+        match self.renew().await {
+            Ok(acquired) => acquired,
+            Err(e) => {
+                async_error!(format!("Failed to acquire lease for {}: {}", self.symbol, e));
+                false
+            }
+        }
+    }
+
+    /// 等待直到获得租约，期间收到关闭信号则返回 false
+    pub async fn acquire(&self, shutdown: &mut watch::Receiver<bool>) -> bool {
+        loop {
+            if *shutdown.borrow() {
+                return false;
+            }
+            if self.try_acquire().await {
+                async_info!(format!("Node {} acquired lease for {}", self.node_id, self.symbol));
+                return true;
+            }
+
+            tokio::select! {
+                _ = tokio::time::sleep(self.renew_interval) => {}
+                changed = shutdown.changed() => {
+                    if changed.is_err() {
+                        return false;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 持续续约，租约被其他节点取得或在有效期内一直续约失败时返回
+    pub async fn hold(&self) {
+        // 本地记录的租约到期时间，留出一个续约间隔的余量以应对时钟误差
+        let mut expires_at = Instant::now() + self.ttl.saturating_sub(self.renew_interval);
+
+        loop {
+            tokio::time::sleep(self.renew_interval).await;
+
+            match self.renew().await {
+                Ok(true) => {
+                    expires_at = Instant::now() + self.ttl.saturating_sub(self.renew_interval);
+                }
+                Ok(false) => {
+                    async_error!(format!("Lease for {} taken over by another node", self.symbol));
+                    return;
+                }
+                Err(e) => {
+                    async_error!(format!("Failed to renew lease for {}: {}", self.symbol, e));
+                    if Instant::now() >= expires_at {
+                        return;
+                    }
+                }
+            }
+        }
+    }
+
+    /// 释放租约，便于其他节点立即接管
+    pub async fn release(&self) {
+        let released = match get_database() {
+            Ok(db) => SymbolLease::release(&db, &self.symbol, &self.node_id).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = released {
+            async_error!(format!("Failed to release lease for {}: {}", self.symbol, e));
+        }
+    }
+}
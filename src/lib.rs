@@ -9,9 +9,28 @@ pub mod date;
 pub mod config;
 pub mod topic;
 mod spot_log;
-mod kafka;
+pub mod envelope;
+pub mod execution;
+pub mod dedup;
+pub mod dead_letter;
+pub mod kafka;
 
 pub mod fbs;
 pub mod db_pool;
+pub mod migrate;
 pub mod model;
 pub mod network;
+pub mod node;
+pub mod lease;
+pub mod snapshot;
+pub mod market_data;
+pub mod ticker;
+pub mod kline;
+pub mod supervisor;
+pub mod http;
+pub mod ws;
+pub mod admin;
+pub mod metrics;
+pub mod health;
+pub mod persistence;
+pub mod bootstrap;
@@ -1,10 +1,19 @@
-use tklog::{async_info, Format, ASYNC_LOG, LEVEL};
-use spot_match::engine::Engine;
-use spot_match::model::config_symbol_matching::ConfigSymbolMatching;
+use tklog::{async_error, async_info, Format, ASYNC_LOG, LEVEL};
+use clap::{Arg, ArgAction, Command};
+use spot_match::config::Config;
+use spot_match::db_pool::get_database;
+use spot_match::dead_letter::{self, DeadLetterReason};
+use spot_match::http::{self, ApiState};
+use spot_match::kafka::create_producer;
+use spot_match::migrate;
+use spot_match::node::init_node_id;
+use spot_match::supervisor::Supervisor;
 use spot_match::network::{get_ip_addresses};
 use tokio::signal;
+use tokio::sync::watch;
 
 #[tokio::main]
+#[allow(clippy::borrow_interior_mutable_const)]
 async fn main() {
     // 初始化日志
     ASYNC_LOG
@@ -19,50 +28,184 @@ async fn main() {
     // let allowed_mac = "aa:bb:cc:dd:ee:ff";
     // check_mac_address(allowed_mac);
 
-    let config_symbol_matching = get_config_symbol_matching().await;
+    // 节点标识：命令行 --node-id > 环境变量 SPOT_MATCH_NODE_ID > 配置文件
+    let matches = Command::new("spot-match")
+        .arg(
+            Arg::new("node-id")
+                .long("node-id")
+                .value_name("NODE_ID")
+                .help("Node identity matched against config_symbol_matching.server"),
+        )
+        .arg(
+            Arg::new("migrate")
+                .long("migrate")
+                .action(ArgAction::SetTrue)
+                .help("Run pending database migrations before starting"),
+        )
+        .subcommand(Command::new("migrate").about("Run pending database migrations and exit"))
+        .subcommand(
+            Command::new("reinject")
+                .about("Copy messages from a symbol's dead-letter topic back to its input topic and exit")
+                .arg(Arg::new("symbol").long("symbol").value_name("SYMBOL").required(true).help("Symbol such as BTC/USDT"))
+                .arg(
+                    Arg::new("from-offset")
+                        .long("from-offset")
+                        .value_name("OFFSET")
+                        .value_parser(clap::value_parser!(i64))
+                        .default_value("0")
+                        .help("First dead-letter offset to reinject"),
+                )
+                .arg(
+                    Arg::new("limit")
+                        .long("limit")
+                        .value_name("COUNT")
+                        .value_parser(clap::value_parser!(usize))
+                        .help("Reinject at most this many messages"),
+                )
+                .arg(
+                    Arg::new("reason")
+                        .long("reason")
+                        .value_name("REASON")
+                        .value_parser(["malformed", "unknown_type", "validation_failed"])
+                        .help("Only reinject messages dead-lettered for this reason"),
+                ),
+        )
+        .get_matches();
+
+    // 迁移子命令：执行完即退出，不启动撮合
+    if matches.subcommand_matches("migrate").is_some() {
+        match run_migrations().await {
+            Ok(count) => println!("Database migrations are up to date ({} versions)", count),
+            Err(e) => {
+                eprintln!("Database migration failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    // 重新注入子命令：将死信写回输入主题后退出
+    if let Some(reinject) = matches.subcommand_matches("reinject") {
+        let brokers = Config::global().kafka_config.brokers.clone();
+        let symbol = reinject.get_one::<String>("symbol").cloned().unwrap_or_default();
+        let from_offset = reinject.get_one::<i64>("from-offset").copied().unwrap_or_default();
+        let limit = reinject.get_one::<usize>("limit").copied();
+        // 无法识别的原因不能当作未指定，否则会重新注入整个死信主题
+        let reason = match reinject.get_one::<String>("reason") {
+            Some(label) => match DeadLetterReason::from_label(label) {
+                Some(reason) => Some(reason),
+                None => {
+                    eprintln!("Unknown dead letter reason: {}", label);
+                    std::process::exit(1);
+                }
+            },
+            None => None,
+        };
+        let result = tokio::task::spawn_blocking(move || {
+            dead_letter::reinject(&brokers, &symbol, from_offset, limit, reason)
+        }).await;
+        match result {
+            Ok(Ok(count)) => println!("Reinjected {} dead letters", count),
+            Ok(Err(e)) => {
+                eprintln!("Reinject failed: {}", e);
+                std::process::exit(1);
+            }
+            Err(e) => {
+                eprintln!("Reinject failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+    match init_node_id(matches.get_one::<String>("node-id").cloned()) {
+        Some(node_id) => async_info!("当前节点 ", node_id),
+        // 未配置节点标识且交易对来自数据库时按本机 IP 分配
+        None if Config::global().symbol_config.source.uses_db() => log_ip_addresses().await,
+        None => {}
+    }
 
-    // 创建所有引擎的句柄
-    let mut engine_handles = Vec::new();
+    // 启动参数 --migrate：先补齐数据库结构，失败时不启动
+    if matches.get_flag("migrate") {
+        match run_migrations().await {
+            Ok(count) => async_info!(format!("Database migrations are up to date ({} versions)", count)),
+            Err(e) => panic!("数据库迁移失败: {}", e),
+        }
+    }
 
-    // 启动每个交易对的撮合引擎
-    for matching in config_symbol_matching {
-        let symbol = matching.base.clone() + "/" + &matching.quote;
-        let mut engine = Engine::new(symbol, matching.base.clone(), matching.quote.clone());
+    // 启动引擎监管器，由它按 config_symbol_matching 启停每个交易对的撮合引擎
+    let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+    let supervisor = Supervisor::new();
+
+    // 查询接口读取引擎写入的状态和行情视图，与撮合互不阻塞
+    let http_server = Config::global().http_config.enabled.then(|| {
+        let config = Config::global();
+        // 管理指令经交易对的输入主题下发给引擎，配置了令牌时才创建生产者
+        let admin_token = config.http_config.admin_token.clone().filter(|token| !token.is_empty());
+        let producer = admin_token.as_ref().and_then(|_| create_producer(&config.kafka_config.brokers));
+        let state = ApiState {
+            statuses: supervisor.statuses(),
+            market_data: supervisor.market_data(),
+            admin_token,
+            producer,
+            health: supervisor.health(),
+        };
+        tokio::spawn(http::serve(state, shutdown_receiver.clone()))
+    });
+    let supervisor = tokio::spawn(supervisor.run(shutdown_receiver));
 
-        // 如果需要从Config中获取数据给engine使用，可以在这里先获取并复制数据，如：
-        // let config_data = {
-        //     let guard = Config::global();
-        //     // 提取所需数据，例如：
-        //     guard.some_field.clone()
-        // };
+    // 等待中断信号
+    wait_for_shutdown_signal().await;
+    async_info!("Shutdown signal received, initiating graceful shutdown...");
 
-        let handle = tokio::spawn(async move {
-            let _ = engine.run().await;
-        });
-        engine_handles.push(handle);
+    // 通知监管器停止所有引擎，每个引擎内部按 engine_config.shutdown_timeout_ms 限时排空
+    let _ = shutdown_sender.send(true);
+    if let Err(e) = supervisor.await {
+        async_error!(format!("Supervisor task error: {}", e));
     }
-
-    // 等待中断信号
-    match signal::ctrl_c().await {
-        Ok(()) => {
-            async_info!("Shutdown signal received, initiating graceful shutdown...");
-            // 在这里添加优雅关闭的逻辑
-        }
-        Err(err) => {
-            async_info!("Error listening for shutdown signal: {}", err);
+    if let Some(http_server) = http_server {
+        if let Err(e) = http_server.await {
+            async_error!(format!("HTTP server task error: {}", e));
         }
     }
+}
+
+// 等待 Ctrl-C 或 SIGTERM
+async fn wait_for_shutdown_signal() {
+    let ctrl_c = async {
+        if let Err(err) = signal::ctrl_c().await {
+            async_info!(format!("Error listening for shutdown signal: {}", err));
+            std::future::pending::<()>().await;
+        }
+    };
 
-    // 等待所有引擎完成
-    for handle in engine_handles {
-        if let Err(e) = handle.await {
-            async_info!("Engine task error: {}", e);
+    #[cfg(unix)]
+    let terminate = async {
+        match signal::unix::signal(signal::unix::SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                sigterm.recv().await;
+            }
+            Err(err) => {
+                async_info!(format!("Error listening for SIGTERM: {}", err));
+                std::future::pending::<()>().await;
+            }
         }
+    };
+
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {},
+        _ = terminate => {},
     }
 }
 
-async fn get_config_symbol_matching() -> Vec<ConfigSymbolMatching> {
+// 在业务表所在的数据库上执行尚未执行的迁移
+async fn run_migrations() -> anyhow::Result<usize> {
+    let db = get_database()?;
+    migrate::run(&db).await
+}
 
+async fn log_ip_addresses() {
     let interfaces = match get_ip_addresses() {
         Ok(result) => result,
         Err(e) => {
@@ -77,19 +220,6 @@ async fn get_config_symbol_matching() -> Vec<ConfigSymbolMatching> {
     if interfaces.is_empty() {
         panic!("获取机器IP为空，程序终止");
     }
-
-    let config_symbol_matching = match ConfigSymbolMatching::get_configs_by_servers(interfaces).await {
-        Ok(matching) => matching,
-        Err(e) => {
-            panic!("获取matching异常，程序终止 {}", e);
-        }
-    };
-
-    if config_symbol_matching.is_empty() {
-        panic!("获取matching为空，程序终止");
-    }
-
-    config_symbol_matching
 }
 
 #[cfg(test)]
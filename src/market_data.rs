@@ -0,0 +1,484 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use flatbuffers::FlatBufferBuilder;
+use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use crate::config::MessageFormat;
+use crate::fbs::depth_generated::depth::{
+    DepthKind as FbsDepthKind, DepthUpdate as FbsDepthUpdate, DepthUpdateArgs,
+    PriceLevel as FbsPriceLevel, PriceLevelArgs,
+};
+use crate::order::{Order, OrderType, Side};
+use crate::order_book::{BookEvent, BookEventKind, Depth, OrderBook, PriceLevel};
+use crate::ticker::Ticker;
+use crate::trade::Trade;
+
+// 深度消息类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum DepthKind {
+    //增量更新：只包含发生变化的档位，数量为 0 表示档位被移除
+    Update,
+    //全量快照：用于下游重新同步
+    Snapshot,
+}
+
+impl fmt::Display for DepthKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind_str = match self {
+            DepthKind::Update => "Update",
+            DepthKind::Snapshot => "Snapshot",
+        };
+        write!(f, "{}", kind_str)
+    }
+}
+
+// 深度行情消息。增量更新的 seq_id 逐条递增；快照的 seq_id 等于其已包含的最后一条增量，
+// 下游以快照为基准，依次应用 seq_id 更大的增量，发现序列号不连续时等待下一次快照重新同步
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DepthUpdate {
+    pub kind: DepthKind,
+    pub symbol: String,
+    pub seq_id: u64,
+    pub timestamp: u64,   // 毫秒级时间戳
+    pub bids: Vec<PriceLevel>,
+    pub asks: Vec<PriceLevel>,
+}
+
+impl DepthUpdate {
+    pub fn new(kind: DepthKind, symbol: &str, seq_id: u64, timestamp: u64, depth: Depth) -> Self {
+        DepthUpdate {
+            kind,
+            symbol: symbol.to_string(),
+            seq_id,
+            timestamp,
+            bids: depth.bids,
+            asks: depth.asks,
+        }
+    }
+
+    /// 按配置的消息格式编码
+    pub fn encode(&self, format: MessageFormat) -> Result<Vec<u8>, serde_json::Error> {
+        match format {
+            MessageFormat::Json => serde_json::to_vec(self),
+            MessageFormat::Flatbuffers => Ok(self.to_flatbuffer()),
+        }
+    }
+
+    /// 从 FlatBuffers 数据解析出 DepthUpdate 实例
+    pub fn parse_depth_flatbuffer(data: &[u8]) -> Result<Self, &'static str> {
+        let fbs_update = flatbuffers::root::<FbsDepthUpdate>(data)
+            .map_err(|_| "Failed to parse FlatBuffer data as DepthUpdate")?;
+
+        let levels = |levels: Option<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<FbsPriceLevel<'_>>>>| {
+            levels.map(|levels| {
+                levels.iter()
+                    .map(|level| PriceLevel {
+                        price: level.price(),
+                        quantity: level.quantity(),
+                        order_count: level.order_count() as usize,
+                    })
+                    .collect()
+            }).unwrap_or_default()
+        };
+
+        Ok(DepthUpdate {
+            kind: match fbs_update.kind() {
+                FbsDepthKind::Update => DepthKind::Update,
+                FbsDepthKind::Snapshot => DepthKind::Snapshot,
+                _ => return Err("Invalid depth kind in FlatBuffer data"),
+            },
+            symbol: fbs_update.symbol().unwrap_or_default().to_string(),
+            seq_id: fbs_update.seq_id(),
+            timestamp: fbs_update.timestamp(),
+            bids: levels(fbs_update.bids()),
+            asks: levels(fbs_update.asks()),
+        })
+    }
+
+    /// 将 DepthUpdate 实例序列化为 FlatBuffers 格式
+    pub fn to_flatbuffer(&self) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::with_capacity(1024);
+
+        let mut levels = |levels: &[PriceLevel]| {
+            let levels: Vec<_> = levels.iter()
+                .map(|level| FbsPriceLevel::create(
+                    &mut builder,
+                    &PriceLevelArgs {
+                        price: level.price,
+                        quantity: level.quantity,
+                        order_count: level.order_count as u64,
+                    },
+                ))
+                .collect();
+            builder.create_vector(&levels)
+        };
+        let bids = levels(&self.bids);
+        let asks = levels(&self.asks);
+        let symbol = builder.create_string(&self.symbol);
+
+        let update = FbsDepthUpdate::create(
+            &mut builder,
+            &DepthUpdateArgs {
+                kind: match self.kind {
+                    DepthKind::Update => FbsDepthKind::Update,
+                    DepthKind::Snapshot => FbsDepthKind::Snapshot,
+                },
+                symbol: Some(symbol),
+                seq_id: self.seq_id,
+                timestamp: self.timestamp,
+                bids: Some(bids),
+                asks: Some(asks),
+            },
+        );
+
+        builder.finish(update, None);
+
+        builder.finished_data().to_vec()
+    }
+}
+
+// 逐笔订单簿消息。seq_id 按交易对逐条递增，下游从头按序应用即可重建完整订单簿
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookEvent {
+    pub symbol: String,
+    pub seq_id: u64,
+    pub timestamp: u64,   // 毫秒级时间戳
+    pub kind: BookEventKind,
+    pub order_id: u64,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+impl OrderBookEvent {
+    pub fn new(symbol: &str, seq_id: u64, timestamp: u64, event: BookEvent) -> Self {
+        OrderBookEvent {
+            symbol: symbol.to_string(),
+            seq_id,
+            timestamp,
+            kind: event.kind,
+            order_id: event.order_id,
+            side: event.side,
+            price: event.price,
+            quantity: event.quantity,
+        }
+    }
+}
+
+// 推送队列的默认长度
+const DEFAULT_STREAM_CAPACITY: usize = 1024;
+
+// 视图的增量变化，推送给订阅者。视图在写锁内更新并发出变化，订阅者在读锁内取快照并订阅，
+// 因此快照之后收到的变化恰好是快照中尚未包含的部分
+#[derive(Debug, Clone)]
+pub enum MarketEvent {
+    //深度变化，seq_id 与深度行情主题一致
+    Depth { seq_id: u64, changes: Depth },
+    Trade { seq_id: u64, trade: Trade },
+    Ticker { seq_id: u64, ticker: Ticker },
+    //视图已按订单簿重置（引擎重启或接管），订阅者需要重新取快照
+    Reset,
+}
+
+/// 单个交易对的查询视图：聚合深度、挂单、最近成交和行情。
+/// 由撮合任务在每条消息处理后按增量更新，查询时只需短暂持有读锁，不经过订单簿锁
+#[derive(Debug)]
+pub struct SymbolMarket {
+    bids: BTreeMap<Reverse<OrderedFloat<f64>>, PriceLevel>,
+    asks: BTreeMap<OrderedFloat<f64>, PriceLevel>,
+    orders: HashMap<u64, Order>,
+    trades: VecDeque<Trade>,
+    trades_capacity: usize,
+    ticker: Option<Ticker>,
+    depth_seq_id: u64,
+    trade_seq_id: u64,
+    ticker_seq_id: u64,
+    halted: bool,
+    events: broadcast::Sender<MarketEvent>,
+}
+
+impl Default for SymbolMarket {
+    fn default() -> Self {
+        SymbolMarket::new(DEFAULT_STREAM_CAPACITY)
+    }
+}
+
+impl SymbolMarket {
+    /// `stream_capacity` 为推送队列长度，订阅者落后超过该长度时需要重新同步
+    pub fn new(stream_capacity: usize) -> Self {
+        SymbolMarket {
+            bids: BTreeMap::new(),
+            asks: BTreeMap::new(),
+            orders: HashMap::new(),
+            trades: VecDeque::new(),
+            trades_capacity: 0,
+            ticker: None,
+            depth_seq_id: 0,
+            trade_seq_id: 0,
+            ticker_seq_id: 0,
+            halted: false,
+            events: broadcast::channel(stream_capacity.max(1)).0,
+        }
+    }
+
+    /// 用订单簿的全量状态重置视图，引擎启动时调用。`depth_seq_id` 为订单簿已包含的最后一条深度增量
+    pub fn reset(&mut self, order_book: &OrderBook, trades_capacity: usize, depth_seq_id: u64) {
+        let depth = order_book.depth(usize::MAX);
+        let (bids, asks) = order_book.resting_orders();
+
+        self.bids = depth.bids.into_iter().map(|level| (Reverse(OrderedFloat(level.price)), level)).collect();
+        self.asks = depth.asks.into_iter().map(|level| (OrderedFloat(level.price), level)).collect();
+        self.orders = bids.into_iter().chain(asks).map(|order| (order.id, order)).collect();
+        self.trades_capacity = trades_capacity;
+        // 容量变小时丢弃最早的成交，保留最近的
+        while self.trades.len() > trades_capacity {
+            self.trades.pop_front();
+        }
+        self.depth_seq_id = depth_seq_id;
+        // 没有订阅者时发送失败，忽略即可
+        let _ = self.events.send(MarketEvent::Reset);
+    }
+
+    /// 应用序列号为 `seq_id` 的深度变化，数量为 0 的档位被移除
+    pub fn apply_depth(&mut self, changes: &Depth, seq_id: u64) {
+        for level in &changes.bids {
+            let key = Reverse(OrderedFloat(level.price));
+            if level.quantity > 0.0 {
+                self.bids.insert(key, level.clone());
+            } else {
+                self.bids.remove(&key);
+            }
+        }
+        for level in &changes.asks {
+            let key = OrderedFloat(level.price);
+            if level.quantity > 0.0 {
+                self.asks.insert(key, level.clone());
+            } else {
+                self.asks.remove(&key);
+            }
+        }
+        self.depth_seq_id = seq_id;
+        let _ = self.events.send(MarketEvent::Depth { seq_id, changes: changes.clone() });
+    }
+
+    /// 应用逐笔变化维护挂单，`incoming` 为产生这些变化的订单，用于补全新挂单的完整信息
+    pub fn apply_book_event(&mut self, event: &BookEvent, incoming: Option<&Order>) {
+        match event.kind {
+            BookEventKind::Add => {
+                let order = match incoming {
+                    Some(incoming) if incoming.id == event.order_id => Order { quantity: event.quantity, ..incoming.clone() },
+                    _ => Order {
+                        id: event.order_id,
+                        user_id: 0,
+                        price: event.price,
+                        quantity: event.quantity,
+                        timestamp: incoming.map_or(0, |incoming| incoming.timestamp),
+                        order_type: OrderType::Limit,
+                        side: event.side,
+                        client_order_id: None,
+                        filled_quantity: 0.0,
+                    },
+                };
+                self.orders.insert(event.order_id, order);
+            }
+            BookEventKind::Reduce | BookEventKind::Execute => {
+                if let Some(order) = self.orders.get_mut(&event.order_id) {
+                    order.quantity -= event.quantity;
+                    if order.quantity <= 0.0 {
+                        self.orders.remove(&event.order_id);
+                    }
+                }
+            }
+            BookEventKind::Delete => {
+                self.orders.remove(&event.order_id);
+            }
+        }
+    }
+
+    /// 记录成交，只保留最近的若干笔
+    pub fn add_trade(&mut self, trade: Trade) {
+        if self.trades.len() >= self.trades_capacity {
+            self.trades.pop_front();
+        }
+        if self.trades_capacity > 0 {
+            self.trades.push_back(trade.clone());
+        }
+        self.trade_seq_id += 1;
+        let _ = self.events.send(MarketEvent::Trade { seq_id: self.trade_seq_id, trade });
+    }
+
+    pub fn update_ticker(&mut self, ticker: Ticker) {
+        self.ticker = Some(ticker.clone());
+        self.ticker_seq_id += 1;
+        let _ = self.events.send(MarketEvent::Ticker { seq_id: self.ticker_seq_id, ticker });
+    }
+
+    /// 交易对被管理指令暂停或恢复撮合
+    pub fn set_halted(&mut self, halted: bool) {
+        self.halted = halted;
+    }
+
+    pub fn halted(&self) -> bool {
+        self.halted
+    }
+
+    /// 订阅视图的增量变化，应与取快照在同一次读锁内进行
+    pub fn subscribe(&self) -> broadcast::Receiver<MarketEvent> {
+        self.events.subscribe()
+    }
+
+    pub fn depth_seq_id(&self) -> u64 {
+        self.depth_seq_id
+    }
+
+    /// 最后一笔成交的序列号，按交易对在本进程内递增
+    pub fn trade_seq_id(&self) -> u64 {
+        self.trade_seq_id
+    }
+
+    /// 最后一次行情的序列号，按交易对在本进程内递增
+    pub fn ticker_seq_id(&self) -> u64 {
+        self.ticker_seq_id
+    }
+
+    /// 买卖双方前 `limit` 档的聚合深度
+    pub fn depth(&self, limit: usize) -> Depth {
+        Depth {
+            bids: self.bids.values().take(limit).cloned().collect(),
+            asks: self.asks.values().take(limit).cloned().collect(),
+        }
+    }
+
+    pub fn order(&self, order_id: u64) -> Option<Order> {
+        self.orders.get(&order_id).cloned()
+    }
+
+    /// 最近 `limit` 笔成交，最新的在前
+    pub fn trades(&self, limit: usize) -> Vec<Trade> {
+        self.trades.iter().rev().take(limit).cloned().collect()
+    }
+
+    pub fn ticker(&self) -> Option<Ticker> {
+        self.ticker.clone()
+    }
+}
+
+/// 各交易对的查询视图，由撮合任务写入，供查询接口读取
+#[derive(Debug, Clone)]
+pub struct MarketDataStore {
+    symbols: Arc<RwLock<HashMap<String, Arc<RwLock<SymbolMarket>>>>>,
+    stream_capacity: usize,
+}
+
+impl Default for MarketDataStore {
+    fn default() -> Self {
+        MarketDataStore::with_stream_capacity(DEFAULT_STREAM_CAPACITY)
+    }
+}
+
+impl MarketDataStore {
+    pub fn new() -> Self {
+        MarketDataStore::default()
+    }
+
+    /// `stream_capacity` 为每个交易对推送队列的长度
+    pub fn with_stream_capacity(stream_capacity: usize) -> Self {
+        MarketDataStore {
+            symbols: Arc::new(RwLock::new(HashMap::new())),
+            stream_capacity,
+        }
+    }
+
+    /// 取得交易对的视图，不存在时创建，供撮合任务写入
+    pub fn symbol(&self, symbol: &str) -> Arc<RwLock<SymbolMarket>> {
+        self.symbols.write().expect("market data poisoned")
+            .entry(symbol.to_string())
+            .or_insert_with(|| Arc::new(RwLock::new(SymbolMarket::new(self.stream_capacity))))
+            .clone()
+    }
+
+    /// 读取交易对的视图，交易对不存在时返回 None
+    pub fn read<T>(&self, symbol: &str, f: impl FnOnce(&SymbolMarket) -> T) -> Option<T> {
+        let market = self.symbols.read().expect("market data poisoned").get(symbol).cloned()?;
+        let market = market.read().expect("market data poisoned");
+        Some(f(&market))
+    }
+
+    pub fn symbols(&self) -> Vec<String> {
+        self.symbols.read().expect("market data poisoned").keys().cloned().collect()
+    }
+
+    /// 交易对停止撮合后移除其视图
+    pub fn remove(&self, symbol: &str) {
+        self.symbols.write().expect("market data poisoned").remove(symbol);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_depth_update_flatbuffer_round_trip() {
+        let update = DepthUpdate {
+            kind: DepthKind::Update,
+            symbol: "BTC/USDT".to_string(),
+            seq_id: 42,
+            timestamp: 1_700_000_000_000,
+            bids: vec![PriceLevel { price: 100.5, quantity: 2.0, order_count: 3 }],
+            asks: vec![
+                PriceLevel { price: 101.0, quantity: 0.0, order_count: 0 },
+                PriceLevel { price: 102.0, quantity: 1.5, order_count: 1 },
+            ],
+        };
+
+        let data = update.encode(MessageFormat::Flatbuffers).unwrap();
+        assert_eq!(DepthUpdate::parse_depth_flatbuffer(&data).unwrap(), update);
+
+        let json = update.encode(MessageFormat::Json).unwrap();
+        assert_eq!(serde_json::from_slice::<DepthUpdate>(&json).unwrap(), update);
+    }
+
+    #[tokio::test]
+    async fn test_symbol_market_follows_order_book() {
+        let mut order_book = OrderBook::new();
+        let mut market = SymbolMarket::default();
+        order_book.add_order(Order::new(1, 101.0, 1.0, OrderType::Limit, Side::Sell));
+        market.reset(&order_book, 10, 0);
+        order_book.take_depth_changes();
+        order_book.take_book_events();
+
+        let orders = [
+            Order::new(2, 102.0, 2.0, OrderType::Limit, Side::Sell),
+            Order::new(3, 101.0, 1.5, OrderType::Limit, Side::Buy),
+        ];
+        for (seq_id, order) in (1..).zip(orders) {
+            let results = order_book.add_order(order.clone());
+            market.apply_depth(&order_book.take_depth_changes(), seq_id);
+            for event in order_book.take_book_events() {
+                market.apply_book_event(&event, Some(&order));
+            }
+            for trade in results.into_iter().filter_map(|result| result.trade) {
+                market.add_trade(trade);
+            }
+        }
+
+        assert_eq!(market.depth(usize::MAX), order_book.depth(usize::MAX));
+        let (bids, asks) = order_book.resting_orders();
+        for order in bids.iter().chain(&asks) {
+            let viewed = market.order(order.id).unwrap();
+            assert_eq!((viewed.user_id, viewed.quantity), (order.user_id, order.quantity));
+        }
+        assert_eq!(market.trades(10).len(), 1);
+
+        // 重置时容量变小，只保留最近的成交
+        for timestamp in [1, 2] {
+            market.add_trade(Trade { buy_order_id: 3, sell_order_id: 1, price: 101.0, quantity: 0.1, timestamp });
+        }
+        market.reset(&order_book, 2, 2);
+        assert_eq!(market.trades(10).iter().map(|trade| trade.timestamp).collect::<Vec<_>>(), vec![2, 1]);
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Write;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::Duration;
+use once_cell::sync::Lazy;
+use crate::dead_letter::DeadLetterReason;
+use crate::order::RejectReason;
+
+// 按交易对登记的指标，引擎启动时创建，交易对停止撮合后移除
+static REGISTRY: Lazy<RwLock<HashMap<String, Arc<SymbolMetrics>>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
+// 撮合延迟直方图的桶上界（秒）
+const LATENCY_BUCKETS: [f64; 12] = [0.0001, 0.00025, 0.0005, 0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 1.0];
+
+/// 固定桶的直方图，每个桶只记录落入该桶的次数，输出时再累加
+#[derive(Debug)]
+pub struct Histogram {
+    buckets: [AtomicU64; LATENCY_BUCKETS.len()],
+    count: AtomicU64,
+    sum_nanos: AtomicU64,
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Histogram {
+            buckets: std::array::from_fn(|_| AtomicU64::new(0)),
+            count: AtomicU64::new(0),
+            sum_nanos: AtomicU64::new(0),
+        }
+    }
+}
+
+impl Histogram {
+    pub fn observe(&self, duration: Duration) {
+        let seconds = duration.as_secs_f64();
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|bound| seconds <= *bound) {
+            self.buckets[i].fetch_add(1, Ordering::Relaxed);
+        }
+        self.count.fetch_add(1, Ordering::Relaxed);
+        self.sum_nanos.fetch_add(duration.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+/// 单个交易对的指标，撮合任务只做原子累加，不影响撮合路径
+#[derive(Debug, Default)]
+pub struct SymbolMetrics {
+    pub new_orders_in: AtomicU64,       // 收到的新订单
+    pub cancel_orders_in: AtomicU64,    // 收到的撤单
+    pub trades_out: AtomicU64,          // 产生的成交
+    rejects: Mutex<BTreeMap<&'static str, u64>>,
+    dead_letters: Mutex<BTreeMap<&'static str, u64>>,
+    pub produce_errors: AtomicU64,      // 生产者拒绝入队的消息
+    pub bid_levels: AtomicU64,
+    pub ask_levels: AtomicU64,
+    pub book_orders: AtomicU64,         // 挂单总数
+    pub queue_length: AtomicU64,        // 待撮合 channel 中积压的消息
+    pub queue_capacity: AtomicU64,
+    pub consumer_lag: AtomicI64,        // 分区最新 offset 与已消费 offset 之差
+    pub match_latency: Histogram,       // 从消费到结果进入生产者队列
+}
+
+impl SymbolMetrics {
+    pub fn reject(&self, reason: &RejectReason) {
+        *self.rejects.lock().expect("metrics poisoned").entry(reason.label()).or_default() += 1;
+    }
+
+    pub fn dead_letter(&self, reason: DeadLetterReason) {
+        *self.dead_letters.lock().expect("metrics poisoned").entry(reason.label()).or_default() += 1;
+    }
+
+    pub fn set_book(&self, (bid_levels, ask_levels): (usize, usize), orders: usize) {
+        self.bid_levels.store(bid_levels as u64, Ordering::Relaxed);
+        self.ask_levels.store(ask_levels as u64, Ordering::Relaxed);
+        self.book_orders.store(orders as u64, Ordering::Relaxed);
+    }
+}
+
+/// 取得交易对的指标，不存在时创建
+pub fn symbol(symbol: &str) -> Arc<SymbolMetrics> {
+    if let Some(metrics) = REGISTRY.read().expect("metrics poisoned").get(symbol) {
+        return metrics.clone();
+    }
+    REGISTRY.write().expect("metrics poisoned").entry(symbol.to_string()).or_default().clone()
+}
+
+/// 交易对停止撮合后移除其指标
+pub fn remove(symbol: &str) {
+    REGISTRY.write().expect("metrics poisoned").remove(symbol);
+}
+
+// 输出一个指标族：HELP、TYPE 以及各交易对的取值
+fn family(out: &mut String, name: &str, kind: &str, help: &str, samples: &[(String, u64)]) {
+    let _ = writeln!(out, "# HELP {} {}", name, help);
+    let _ = writeln!(out, "# TYPE {} {}", name, kind);
+    for (labels, value) in samples {
+        let _ = writeln!(out, "{}{{{}}} {}", name, labels, value);
+    }
+}
+
+fn label_value(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// 以 Prometheus 文本格式输出全部指标
+pub fn render() -> String {
+    let registry = REGISTRY.read().expect("metrics poisoned");
+    let mut symbols: Vec<_> = registry.iter().collect();
+    symbols.sort_by(|a, b| a.0.cmp(b.0));
+    let symbol_label = |symbol: &str| format!("symbol=\"{}\"", label_value(symbol));
+    let per_symbol = |f: &dyn Fn(&SymbolMetrics) -> u64| -> Vec<(String, u64)> {
+        symbols.iter().map(|(symbol, metrics)| (symbol_label(symbol), f(metrics))).collect()
+    };
+
+    let mut out = String::new();
+    let mut orders_in = Vec::new();
+    for (symbol, metrics) in &symbols {
+        let labels = symbol_label(symbol);
+        orders_in.push((format!("{},log_type=\"NewOrder\"", labels), metrics.new_orders_in.load(Ordering::Relaxed)));
+        orders_in.push((format!("{},log_type=\"CancelOrder\"", labels), metrics.cancel_orders_in.load(Ordering::Relaxed)));
+    }
+    family(&mut out, "spot_match_orders_in_total", "counter", "Orders received by the matching engine.", &orders_in);
+    family(&mut out, "spot_match_trades_out_total", "counter", "Trades produced by matching.",
+           &per_symbol(&|m| m.trades_out.load(Ordering::Relaxed)));
+
+    let mut rejects = Vec::new();
+    for (symbol, metrics) in &symbols {
+        for (reason, count) in metrics.rejects.lock().expect("metrics poisoned").iter() {
+            rejects.push((format!("{},reason=\"{}\"", symbol_label(symbol), reason), *count));
+        }
+    }
+    family(&mut out, "spot_match_rejects_total", "counter", "Orders rejected before matching, by reason.", &rejects);
+
+    let mut dead_letters = Vec::new();
+    for (symbol, metrics) in &symbols {
+        for (reason, count) in metrics.dead_letters.lock().expect("metrics poisoned").iter() {
+            dead_letters.push((format!("{},reason=\"{}\"", symbol_label(symbol), reason), *count));
+        }
+    }
+    family(&mut out, "spot_match_dead_letters_total", "counter", "Input messages forwarded to the dead-letter topic, by reason.",
+           &dead_letters);
+    family(&mut out, "spot_match_produce_errors_total", "counter", "Messages the Kafka producer refused to queue.",
+           &per_symbol(&|m| m.produce_errors.load(Ordering::Relaxed)));
+
+    let mut levels = Vec::new();
+    for (symbol, metrics) in &symbols {
+        let labels = symbol_label(symbol);
+        levels.push((format!("{},side=\"Buy\"", labels), metrics.bid_levels.load(Ordering::Relaxed)));
+        levels.push((format!("{},side=\"Sell\"", labels), metrics.ask_levels.load(Ordering::Relaxed)));
+    }
+    family(&mut out, "spot_match_book_levels", "gauge", "Price levels in the order book.", &levels);
+    family(&mut out, "spot_match_book_orders", "gauge", "Resting orders in the order book.",
+           &per_symbol(&|m| m.book_orders.load(Ordering::Relaxed)));
+    family(&mut out, "spot_match_queue_length", "gauge", "Messages waiting in the engine channel.",
+           &per_symbol(&|m| m.queue_length.load(Ordering::Relaxed)));
+    family(&mut out, "spot_match_queue_capacity", "gauge", "Capacity of the engine channel.",
+           &per_symbol(&|m| m.queue_capacity.load(Ordering::Relaxed)));
+
+    let _ = writeln!(out, "# HELP spot_match_consumer_lag Messages in the input partition not yet consumed.");
+    let _ = writeln!(out, "# TYPE spot_match_consumer_lag gauge");
+    for (symbol, metrics) in &symbols {
+        let _ = writeln!(out, "spot_match_consumer_lag{{{}}} {}", symbol_label(symbol), metrics.consumer_lag.load(Ordering::Relaxed));
+    }
+
+    let _ = writeln!(out, "# HELP spot_match_match_latency_seconds Time from consuming an input message to queueing its results.");
+    let _ = writeln!(out, "# TYPE spot_match_match_latency_seconds histogram");
+    for (symbol, metrics) in &symbols {
+        let labels = symbol_label(symbol);
+        let histogram = &metrics.match_latency;
+        let mut cumulative = 0;
+        for (bound, bucket) in LATENCY_BUCKETS.iter().zip(&histogram.buckets) {
+            cumulative += bucket.load(Ordering::Relaxed);
+            let _ = writeln!(out, "spot_match_match_latency_seconds_bucket{{{},le=\"{}\"}} {}", labels, bound, cumulative);
+        }
+        let count = histogram.count.load(Ordering::Relaxed);
+        let _ = writeln!(out, "spot_match_match_latency_seconds_bucket{{{},le=\"+Inf\"}} {}", labels, count);
+        let sum = histogram.sum_nanos.load(Ordering::Relaxed) as f64 / 1e9;
+        let _ = writeln!(out, "spot_match_match_latency_seconds_sum{{{}}} {}", labels, sum);
+        let _ = writeln!(out, "spot_match_match_latency_seconds_count{{{}}} {}", labels, count);
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_metrics() {
+        let metrics = symbol("METRICS/TEST");
+        metrics.new_orders_in.fetch_add(3, Ordering::Relaxed);
+        metrics.reject(&RejectReason::Halted);
+        metrics.dead_letter(DeadLetterReason::Malformed);
+        metrics.match_latency.observe(Duration::from_micros(300));
+        metrics.match_latency.observe(Duration::from_secs(2));
+
+        let text = render();
+        assert!(text.contains("spot_match_orders_in_total{symbol=\"METRICS/TEST\",log_type=\"NewOrder\"} 3"));
+        assert!(text.contains("spot_match_rejects_total{symbol=\"METRICS/TEST\",reason=\"halted\"} 1"));
+        assert!(text.contains("spot_match_dead_letters_total{symbol=\"METRICS/TEST\",reason=\"malformed\"} 1"));
+        assert!(text.contains("spot_match_match_latency_seconds_bucket{symbol=\"METRICS/TEST\",le=\"0.0005\"} 1"));
+        assert!(text.contains("spot_match_match_latency_seconds_bucket{symbol=\"METRICS/TEST\",le=\"+Inf\"} 2"));
+
+        remove("METRICS/TEST");
+        assert!(!render().contains("METRICS/TEST"));
+    }
+}
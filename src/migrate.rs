@@ -0,0 +1,26 @@
+use anyhow::Result;
+use sqlx::migrate::Migrator;
+use crate::db_pool::Database;
+
+// 迁移脚本在编译时嵌入，目录位于仓库根目录的 migrations 下，按文件名前缀的版本号依次执行。
+// 两个后端的表结构相同，MySQL 的脚本按其语法单独维护
+static POSTGRESQL_MIGRATOR: Migrator = sqlx::migrate!("migrations/postgresql");
+static MYSQL_MIGRATOR: Migrator = sqlx::migrate!("migrations/mysql");
+
+fn migrator(db: &Database) -> &'static Migrator {
+    match db {
+        Database::Postgresql(_) => &POSTGRESQL_MIGRATOR,
+        Database::Mysql(_) => &MYSQL_MIGRATOR,
+    }
+}
+
+/// 执行尚未执行的迁移，已执行的版本记录在 _sqlx_migrations 表中，重复执行没有影响。
+/// 返回该后端的迁移版本总数
+pub async fn run(db: &Database) -> Result<usize> {
+    let migrator = migrator(db);
+    match db {
+        Database::Postgresql(pool) => migrator.run(pool).await?,
+        Database::Mysql(pool) => migrator.run(pool).await?,
+    }
+    Ok(migrator.iter().count())
+}
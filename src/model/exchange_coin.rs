@@ -1,7 +1,7 @@
 use rust_decimal::Decimal;
 use sqlx::FromRow;
 use serde::{Deserialize, Serialize};
-use crate::db_pool::get_postgresql_pool;
+use crate::db_pool::Database;
 
 #[derive(Debug, FromRow, Serialize, Deserialize)]
 pub struct ExchangeCoin {
@@ -40,13 +40,14 @@ pub struct ExchangeCoin {
 
 impl ExchangeCoin {
     pub async fn get_exchange_coins_by_exchangeable(
+        db: &Database,
         exchangeable_value: i32,
     ) -> Result<Vec<ExchangeCoin>, sqlx::Error> {
-        // let mut conn = get_connection().await?;
-        let coins = sqlx::query_as::<_, ExchangeCoin>("SELECT * FROM exchange_coin WHERE exchangeable = ?")
-            .bind(exchangeable_value)
-            .fetch_all(get_postgresql_pool()?)
-            .await?;
-        Ok(coins)
+        crate::on_database!(db, "SELECT * FROM exchange_coin WHERE exchangeable = ?", |pool, sql| {
+            sqlx::query_as::<_, ExchangeCoin>(&sql)
+                .bind(exchangeable_value)
+                .fetch_all(pool)
+                .await
+        })
     }
 }
@@ -1,7 +1,36 @@
 use sqlx::FromRow;
 use serde::{Deserialize, Serialize};
 use rust_decimal::Decimal;
-use crate::db_pool::{get_postgresql_pool};
+use crate::db_pool::{in_list, Database, DatabaseTransaction};
+
+// exchange_order.status 的取值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeOrderStatus {
+    //交易中
+    Trading = 0,
+    //已完成
+    Completed = 1,
+    //已撤销
+    Canceled = 2,
+    //已超时
+    Overtimed = 3,
+}
+
+// exchange_order.direction 的取值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeOrderDirection {
+    Buy = 0,
+    Sell = 1,
+}
+
+// exchange_order.order_type 的取值
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExchangeOrderType {
+    //市价
+    MarketPrice = 0,
+    //限价
+    LimitPrice = 1,
+}
 
 #[derive(Debug, FromRow, Serialize, Deserialize)]
 pub struct ExchangeOrder {
@@ -26,33 +55,111 @@ pub struct ExchangeOrder {
 
 impl ExchangeOrder {
     /// 根据订单状态查询订单
-    pub async fn get_orders_by_status(status_value: i32) -> Result<Vec<ExchangeOrder>, sqlx::Error> {
-        let orders = sqlx::query_as::<_, ExchangeOrder>(
-            "SELECT * FROM exchange_order WHERE status = ?"
-        )
-            .bind(status_value)
-            .fetch_all(get_postgresql_pool()?)
-            .await?;
-        Ok(orders)
+    pub async fn get_orders_by_status(db: &Database, status_value: i32) -> Result<Vec<ExchangeOrder>, sqlx::Error> {
+        crate::on_database!(db, "SELECT * FROM exchange_order WHERE status = ?", |pool, sql| {
+            sqlx::query_as::<_, ExchangeOrder>(&sql)
+                .bind(status_value)
+                .fetch_all(pool)
+                .await
+        })
     }
 
     /// 根据一组订单状态查询订单
-    pub async fn get_orders_by_status_list(status_values: Vec<i32>) -> Result<Vec<ExchangeOrder>, sqlx::Error> {
+    pub async fn get_orders_by_status_list(db: &Database, status_values: Vec<i32>) -> Result<Vec<ExchangeOrder>, sqlx::Error> {
+        if status_values.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = format!("SELECT * FROM exchange_order WHERE status IN ({})", in_list(status_values.len()));
+        crate::on_database!(db, query, |pool, sql| {
+            let mut query_builder = sqlx::query_as::<_, ExchangeOrder>(&sql);
+            for status in status_values {
+                query_builder = query_builder.bind(status);
+            }
+            query_builder.fetch_all(pool).await
+        })
+    }
+
+    /// 查询交易对处于给定状态的订单，按下单时间排序
+    pub async fn get_orders_by_symbol_and_status_list(
+        db: &Database,
+        symbol: &str,
+        status_values: Vec<i32>,
+    ) -> Result<Vec<ExchangeOrder>, sqlx::Error> {
+        if status_values.is_empty() {
+            return Ok(Vec::new());
+        }
 
-        // 构建 SQL 查询语句
         let query = format!(
-            "SELECT * FROM exchange_order WHERE status IN ({})",
-            status_values.iter().map(|_| "?").collect::<Vec<&str>>().join(", ")
+            "SELECT * FROM exchange_order WHERE symbol = ? AND status IN ({}) ORDER BY time",
+            in_list(status_values.len())
         );
+        crate::on_database!(db, query, |pool, sql| {
+            let mut query_builder = sqlx::query_as::<_, ExchangeOrder>(&sql).bind(symbol);
+            for status in status_values {
+                query_builder = query_builder.bind(status);
+            }
+            query_builder.fetch_all(pool).await
+        })
+    }
 
-        // 构建查询并绑定参数
-        let mut query_builder = sqlx::query_as::<_, ExchangeOrder>(&query);
-        for status in status_values {
-            query_builder = query_builder.bind(status);
-        }
+    /// 查询交易对所有状态的订单号
+    pub async fn get_order_ids_by_symbol(db: &Database, symbol: &str) -> Result<Vec<String>, sqlx::Error> {
+        crate::on_database!(db, "SELECT order_id FROM exchange_order WHERE symbol = ?", |pool, sql| {
+            sqlx::query_scalar::<_, String>(&sql)
+                .bind(symbol)
+                .fetch_all(pool)
+                .await
+        })
+    }
+
+    /// 记录订单的一笔成交：累加成交量和成交额，累计成交量达到委托数量时标记为已完成。
+    /// 累加不是幂等的，调用方需保证同一笔成交只记录一次。
+    /// MySQL 按书写顺序赋值，后面的表达式会读到前面已更新的列，因此先写依赖旧值的列
+    pub async fn apply_trade(
+        tx: &mut DatabaseTransaction,
+        order_id: &str,
+        quantity: Decimal,
+        turnover: Decimal,
+        timestamp: i64,
+    ) -> Result<(), sqlx::Error> {
+        let trading = ExchangeOrderStatus::Trading as i32;
+        let completed = ExchangeOrderStatus::Completed as i32;
+        let query = "UPDATE exchange_order SET \
+             completed_time = CASE WHEN status = ? AND COALESCE(traded_amount, 0) + ? >= amount THEN ? ELSE completed_time END, \
+             status = CASE WHEN status = ? AND COALESCE(traded_amount, 0) + ? >= amount THEN ? ELSE status END, \
+             traded_amount = COALESCE(traded_amount, 0) + ?, \
+             turnover = COALESCE(turnover, 0) + ? \
+             WHERE order_id = ?";
+        crate::on_transaction!(tx, query, |conn, sql| {
+            sqlx::query(&sql)
+                .bind(trading)
+                .bind(quantity)
+                .bind(timestamp)
+                .bind(trading)
+                .bind(quantity)
+                .bind(completed)
+                .bind(quantity)
+                .bind(turnover)
+                .bind(order_id)
+                .execute(conn)
+                .await
+                .map(|_| ())
+        })
+    }
 
-        // 执行查询并获取结果
-        let orders = query_builder.fetch_all(get_postgresql_pool()?).await?;
-        Ok(orders)
+    /// 订单剩余部分被撤销，仍在交易中的订单标记为已撤销，已完成或已撤销的订单不变，可重复调用
+    pub async fn cancel(tx: &mut DatabaseTransaction, order_id: &str, timestamp: i64) -> Result<(), sqlx::Error> {
+        let query = "UPDATE exchange_order SET status = ?, canceled_time = ? WHERE order_id = ? AND status = ?";
+        crate::on_transaction!(tx, query, |conn, sql| {
+            sqlx::query(&sql)
+                .bind(ExchangeOrderStatus::Canceled as i32)
+                .bind(timestamp)
+                .bind(order_id)
+                .bind(ExchangeOrderStatus::Trading as i32)
+                .execute(conn)
+                .await
+                .map(|_| ())
+        })
     }
 }
@@ -1,3 +1,6 @@
 pub mod exchange_coin;
 pub mod config_symbol_matching;
 pub mod exchange_order;
+pub mod symbol_lease;
+pub mod spot_kline;
+pub mod spot_trade;
@@ -0,0 +1,118 @@
+use sqlx::FromRow;
+use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use crate::db_pool::Database;
+use crate::kline::Kline;
+
+/// 已收盘的 K 线。
+///
+/// 依赖的表结构：
+/// ```sql
+/// CREATE TABLE spot_kline (
+///     symbol       VARCHAR(64) NOT NULL,
+///     interval     VARCHAR(8) NOT NULL,
+///     open_time    BIGINT NOT NULL,
+///     close_time   BIGINT NOT NULL,
+///     open         NUMERIC NOT NULL,
+///     high         NUMERIC NOT NULL,
+///     low          NUMERIC NOT NULL,
+///     close        NUMERIC NOT NULL,
+///     volume       NUMERIC NOT NULL,
+///     quote_volume NUMERIC NOT NULL,
+///     trade_count  BIGINT NOT NULL,
+///     PRIMARY KEY (symbol, interval, open_time)
+/// );
+/// ```
+/// MySQL 中 `interval` 是保留字，需加反引号；`NUMERIC` 需写为 `DECIMAL(36, 18)`
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct SpotKline {
+    pub symbol: String,
+    pub interval: String,
+    pub open_time: i64,   // 毫秒级时间戳
+    pub close_time: i64,  // 毫秒级时间戳
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub quote_volume: Decimal,
+    pub trade_count: i64,
+}
+
+impl From<&Kline> for SpotKline {
+    fn from(kline: &Kline) -> Self {
+        let decimal = |value: f64| Decimal::try_from(value).unwrap_or_default();
+        SpotKline {
+            symbol: kline.symbol.clone(),
+            interval: kline.interval.to_string(),
+            open_time: kline.open_time as i64,
+            close_time: kline.close_time as i64,
+            open: decimal(kline.open),
+            high: decimal(kline.high),
+            low: decimal(kline.low),
+            close: decimal(kline.close),
+            volume: decimal(kline.volume),
+            quote_volume: decimal(kline.quote_volume),
+            trade_count: kline.trade_count as i64,
+        }
+    }
+}
+
+impl SpotKline {
+    /// 写入 K 线，同一交易对、周期和开盘时间重复写入时覆盖，K 线按事件时间确定性聚合，从快照重放得到的 K 线与原先相同，覆盖写入不改变结果
+    pub async fn upsert(&self, db: &Database) -> Result<(), sqlx::Error> {
+        let query = match db {
+            Database::Postgresql(_) => {
+                "INSERT INTO spot_kline \
+                 (symbol, interval, open_time, close_time, open, high, low, close, volume, quote_volume, trade_count) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+                 ON CONFLICT (symbol, interval, open_time) DO UPDATE SET \
+                 close_time = EXCLUDED.close_time, open = EXCLUDED.open, high = EXCLUDED.high, low = EXCLUDED.low, \
+                 close = EXCLUDED.close, volume = EXCLUDED.volume, quote_volume = EXCLUDED.quote_volume, \
+                 trade_count = EXCLUDED.trade_count"
+            }
+            Database::Mysql(_) => {
+                "INSERT INTO spot_kline \
+                 (symbol, `interval`, open_time, close_time, open, high, low, close, volume, quote_volume, trade_count) \
+                 VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?) \
+                 ON DUPLICATE KEY UPDATE \
+                 close_time = VALUES(close_time), open = VALUES(open), high = VALUES(high), low = VALUES(low), \
+                 close = VALUES(close), volume = VALUES(volume), quote_volume = VALUES(quote_volume), \
+                 trade_count = VALUES(trade_count)"
+            }
+        };
+        crate::on_database!(db, query, |pool, sql| {
+            sqlx::query(&sql)
+                .bind(&self.symbol)
+                .bind(&self.interval)
+                .bind(self.open_time)
+                .bind(self.close_time)
+                .bind(self.open)
+                .bind(self.high)
+                .bind(self.low)
+                .bind(self.close)
+                .bind(self.volume)
+                .bind(self.quote_volume)
+                .bind(self.trade_count)
+                .execute(pool)
+                .await
+                .map(|_| ())
+        })
+    }
+
+    /// 查询交易对某一周期最近的 K 线，按开盘时间倒序
+    pub async fn get_latest(db: &Database, symbol: &str, interval: &str, limit: i64) -> Result<Vec<SpotKline>, sqlx::Error> {
+        let query = match db {
+            Database::Postgresql(_) => "SELECT * FROM spot_kline WHERE symbol = ? AND interval = ? ORDER BY open_time DESC LIMIT ?",
+            Database::Mysql(_) => "SELECT * FROM spot_kline WHERE symbol = ? AND `interval` = ? ORDER BY open_time DESC LIMIT ?",
+        };
+        crate::on_database!(db, query, |pool, sql| {
+            sqlx::query_as::<_, SpotKline>(&sql)
+                .bind(symbol)
+                .bind(interval)
+                .bind(limit)
+                .fetch_all(pool)
+                .await
+        })
+    }
+}
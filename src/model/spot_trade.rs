@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+use sqlx::{FromRow, Postgres, QueryBuilder};
+use serde::{Deserialize, Serialize};
+use rust_decimal::Decimal;
+use crate::db_pool::{Database, DatabaseTransaction};
+use crate::trade::Trade;
+
+/// 撮合产生的成交，以交易对和撮合结果的序列号为主键，重放产生的重复成交不会重复写入。
+///
+/// 依赖的表结构：
+/// ```sql
+/// CREATE TABLE spot_trade (
+///     symbol        VARCHAR(64) NOT NULL,
+///     seq_id        BIGINT NOT NULL,
+///     buy_order_id  VARCHAR(64) NOT NULL,
+///     sell_order_id VARCHAR(64) NOT NULL,
+///     price         NUMERIC NOT NULL,
+///     quantity      NUMERIC NOT NULL,
+///     timestamp     BIGINT NOT NULL,
+///     PRIMARY KEY (symbol, seq_id)
+/// );
+/// ```
+/// MySQL 中 `NUMERIC` 不指定精度时没有小数位，需写为 `DECIMAL(36, 18)`
+#[derive(Debug, Clone, PartialEq, FromRow, Serialize, Deserialize)]
+pub struct SpotTrade {
+    pub symbol: String,
+    pub seq_id: i64,          // 成交所在撮合结果的序列号
+    pub buy_order_id: String,
+    pub sell_order_id: String,
+    pub price: Decimal,
+    pub quantity: Decimal,
+    pub timestamp: i64,       // 毫秒级时间戳
+}
+
+impl SpotTrade {
+    /// 价格或数量无法表示为 Decimal（NaN、无穷大或超出范围）时返回错误
+    pub fn new(symbol: &str, seq_id: u64, trade: &Trade) -> Result<Self, rust_decimal::Error> {
+        Ok(SpotTrade {
+            symbol: symbol.to_string(),
+            seq_id: seq_id as i64,
+            buy_order_id: trade.buy_order_id.to_string(),
+            sell_order_id: trade.sell_order_id.to_string(),
+            price: Decimal::try_from(trade.price)?,
+            quantity: Decimal::try_from(trade.quantity)?,
+            timestamp: trade.timestamp as i64,
+        })
+    }
+
+    /// 成交额（计价币）
+    pub fn turnover(&self) -> Decimal {
+        self.price * self.quantity
+    }
+
+    /// 批量写入成交，已存在的成交跳过，返回本次实际写入的 (交易对, 序列号)
+    pub async fn insert_batch(tx: &mut DatabaseTransaction, trades: &[SpotTrade]) -> Result<HashSet<(String, i64)>, sqlx::Error> {
+        if trades.is_empty() {
+            return Ok(HashSet::new());
+        }
+        match tx {
+            DatabaseTransaction::Postgresql(tx) => {
+                let mut query_builder: QueryBuilder<Postgres> = QueryBuilder::new(
+                    "INSERT INTO spot_trade (symbol, seq_id, buy_order_id, sell_order_id, price, quantity, timestamp) "
+                );
+                query_builder.push_values(trades, |mut row, trade| {
+                    row.push_bind(&trade.symbol)
+                        .push_bind(trade.seq_id)
+                        .push_bind(&trade.buy_order_id)
+                        .push_bind(&trade.sell_order_id)
+                        .push_bind(trade.price)
+                        .push_bind(trade.quantity)
+                        .push_bind(trade.timestamp);
+                });
+                query_builder.push(" ON CONFLICT (symbol, seq_id) DO NOTHING RETURNING symbol, seq_id");
+
+                let inserted = query_builder.build_query_as::<(String, i64)>()
+                    .fetch_all(&mut ***tx)
+                    .await?;
+                Ok(inserted.into_iter().collect())
+            }
+            // MySQL 没有 RETURNING，逐条写入，按影响行数判断是否为新成交
+            DatabaseTransaction::Mysql(tx) => {
+                let mut inserted = HashSet::new();
+                for trade in trades {
+                    let result = sqlx::query(
+                        "INSERT IGNORE INTO spot_trade (symbol, seq_id, buy_order_id, sell_order_id, price, quantity, timestamp) \
+                         VALUES (?, ?, ?, ?, ?, ?, ?)"
+                    )
+                        .bind(&trade.symbol)
+                        .bind(trade.seq_id)
+                        .bind(&trade.buy_order_id)
+                        .bind(&trade.sell_order_id)
+                        .bind(trade.price)
+                        .bind(trade.quantity)
+                        .bind(trade.timestamp)
+                        .execute(&mut ***tx)
+                        .await?;
+                    if result.rows_affected() == 1 {
+                        inserted.insert((trade.symbol.clone(), trade.seq_id));
+                    }
+                }
+                Ok(inserted)
+            }
+        }
+    }
+
+    /// 查询交易对序列号不小于 `from_seq_id` 的成交，按序列号排序
+    pub async fn get_by_symbol(db: &Database, symbol: &str, from_seq_id: i64, limit: i64) -> Result<Vec<SpotTrade>, sqlx::Error> {
+        let query = "SELECT * FROM spot_trade WHERE symbol = ? AND seq_id >= ? ORDER BY seq_id LIMIT ?";
+        crate::on_database!(db, query, |pool, sql| {
+            sqlx::query_as::<_, SpotTrade>(&sql)
+                .bind(symbol)
+                .bind(from_seq_id)
+                .bind(limit)
+                .fetch_all(pool)
+                .await
+        })
+    }
+}
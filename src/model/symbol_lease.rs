@@ -0,0 +1,88 @@
+use sqlx::FromRow;
+use serde::{Deserialize, Serialize};
+use crate::db_pool::Database;
+
+/// 交易对租约，保证同一时刻一个交易对最多由一个存活节点撮合。
+///
+/// 依赖的表结构：
+/// ```sql
+/// CREATE TABLE symbol_lease (
+///     symbol     VARCHAR(64) PRIMARY KEY,
+///     node_id    VARCHAR(128) NOT NULL,
+///     expires_at BIGINT NOT NULL
+/// );
+/// ```
+#[derive(Debug, FromRow, Serialize, Deserialize)]
+pub struct SymbolLease {
+    pub symbol: String,
+    pub node_id: String,
+    pub expires_at: i64,  // 毫秒级时间戳
+}
+
+impl SymbolLease {
+    /// 获取或续约租约：租约不存在、已过期或本就属于该节点时成功，返回是否持有租约。
+    /// 先按条件续约或接管，没有匹配的行时再插入，行已被其他节点持有时插入被忽略。
+    /// MySQL 连接开启了 CLIENT_FOUND_ROWS，续约时间不变时 UPDATE 的影响行数仍为匹配的行数
+    pub async fn try_acquire(
+        db: &Database,
+        symbol: &str,
+        node_id: &str,
+        now: i64,
+        ttl_ms: i64,
+    ) -> Result<bool, sqlx::Error> {
+        let query = "UPDATE symbol_lease SET node_id = ?, expires_at = ? WHERE symbol = ? AND (node_id = ? OR expires_at < ?)";
+        let updated = crate::on_database!(db, query, |pool, sql| {
+            sqlx::query(&sql)
+                .bind(node_id)
+                .bind(now + ttl_ms)
+                .bind(symbol)
+                .bind(node_id)
+                .bind(now)
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+        })?;
+        if updated == 1 {
+            return Ok(true);
+        }
+
+        let query = match db {
+            Database::Postgresql(_) => "INSERT INTO symbol_lease (symbol, node_id, expires_at) VALUES (?, ?, ?) ON CONFLICT (symbol) DO NOTHING",
+            Database::Mysql(_) => "INSERT IGNORE INTO symbol_lease (symbol, node_id, expires_at) VALUES (?, ?, ?)",
+        };
+        let inserted = crate::on_database!(db, query, |pool, sql| {
+            sqlx::query(&sql)
+                .bind(symbol)
+                .bind(node_id)
+                .bind(now + ttl_ms)
+                .execute(pool)
+                .await
+                .map(|result| result.rows_affected())
+        })?;
+        Ok(inserted == 1)
+    }
+
+    /// 释放本节点持有的租约
+    pub async fn release(db: &Database, symbol: &str, node_id: &str) -> Result<(), sqlx::Error> {
+        let query = "DELETE FROM symbol_lease WHERE symbol = ? AND node_id = ?";
+        crate::on_database!(db, query, |pool, sql| {
+            sqlx::query(&sql)
+                .bind(symbol)
+                .bind(node_id)
+                .execute(pool)
+                .await
+                .map(|_| ())
+        })
+    }
+
+    /// 查询交易对当前的租约
+    pub async fn get_by_symbol(db: &Database, symbol: &str) -> Result<Option<SymbolLease>, sqlx::Error> {
+        let query = "SELECT * FROM symbol_lease WHERE symbol = ?";
+        crate::on_database!(db, query, |pool, sql| {
+            sqlx::query_as::<_, SymbolLease>(&sql)
+                .bind(symbol)
+                .fetch_optional(pool)
+                .await
+        })
+    }
+}
@@ -12,7 +12,7 @@ pub fn get_ip_addresses() -> Result<Vec<String>, io::Error> {
             Ok(ip_addresses)  // 将结果返回给外部
         }
         Err(e) => {
-            Err(io::Error::new(io::ErrorKind::Other, format!("获取机器 IP 异常: {}", e)))
+            Err(io::Error::other(format!("获取机器 IP 异常: {}", e)))
         }
     }
 }
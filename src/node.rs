@@ -0,0 +1,23 @@
+use once_cell::sync::OnceCell;
+use crate::config::Config;
+
+/// 指定节点标识的环境变量
+pub const NODE_ID_ENV: &str = "SPOT_MATCH_NODE_ID";
+
+static NODE_ID: OnceCell<Option<String>> = OnceCell::new();
+
+/// 确定本节点标识，优先级：命令行参数 > 环境变量 > 配置文件 node_config.node_id。
+/// 只在启动时调用一次，之后通过 `node_id` 读取
+pub fn init_node_id(cli_node_id: Option<String>) -> Option<String> {
+    NODE_ID.get_or_init(|| {
+        cli_node_id
+            .or_else(|| std::env::var(NODE_ID_ENV).ok())
+            .or_else(|| Config::global().node_config.node_id.clone())
+            .filter(|node_id| !node_id.trim().is_empty())
+    }).clone()
+}
+
+/// 本节点标识，未配置时返回 None，此时按本机 IP 分配交易对
+pub fn node_id() -> Option<String> {
+    NODE_ID.get().cloned().flatten()
+}
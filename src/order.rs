@@ -1,6 +1,6 @@
 use std::fmt;
 use std::sync::atomic::{AtomicU64, Ordering};
-use flatbuffers::FlatBufferBuilder;
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
 use serde::{Deserialize, Serialize};
 use crate::date::current_timestamp;
 use crate::fbs::order_generated::order::{Order as FbsOrder, OrderArgs, OrderType as FbsOrderType, Side as FbsSide};
@@ -15,6 +15,27 @@ pub enum Side {
     Sell,
 }
 
+impl From<Side> for FbsSide {
+    fn from(side: Side) -> Self {
+        match side {
+            Side::Buy => FbsSide::Buy,
+            Side::Sell => FbsSide::Sell,
+        }
+    }
+}
+
+impl TryFrom<FbsSide> for Side {
+    type Error = &'static str;
+
+    fn try_from(side: FbsSide) -> Result<Self, &'static str> {
+        match side {
+            FbsSide::Buy => Ok(Side::Buy),
+            FbsSide::Sell => Ok(Side::Sell),
+            _ => Err("Invalid side in FlatBuffer data"),
+        }
+    }
+}
+
 impl fmt::Display for Side {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let side_str = match self {
@@ -26,12 +47,33 @@ impl fmt::Display for Side {
 }
 
 // 订单类型
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderType {
     Limit,
     Market,
 }
 
+impl From<OrderType> for FbsOrderType {
+    fn from(order_type: OrderType) -> Self {
+        match order_type {
+            OrderType::Limit => FbsOrderType::Limit,
+            OrderType::Market => FbsOrderType::Market,
+        }
+    }
+}
+
+impl TryFrom<FbsOrderType> for OrderType {
+    type Error = &'static str;
+
+    fn try_from(order_type: FbsOrderType) -> Result<Self, &'static str> {
+        match order_type {
+            FbsOrderType::Limit => Ok(OrderType::Limit),
+            FbsOrderType::Market => Ok(OrderType::Market),
+            _ => Err("Invalid order type in FlatBuffer data"),
+        }
+    }
+}
+
 impl fmt::Display for OrderType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -41,6 +83,73 @@ impl fmt::Display for OrderType {
     }
 }
 
+// 订单在撮合前被拒绝的原因
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RejectReason {
+    InvalidQuantity(f64),
+    InvalidPrice(f64),
+    MarketBuyDisabled,
+    MarketSellDisabled,
+    PriceScale { price: f64, scale: u32 },
+    QuantityScale { quantity: f64, scale: u32 },
+    TurnoverBelowMinimum(f64),
+    QuantityBelowMinimum(f64),
+    QuantityAboveMaximum(f64),
+    //订单簿中已有相同订单号的挂单，例如从数据库恢复后输入主题中重复的新订单
+    DuplicateOrderId,
+    //交易对已被管理指令暂停
+    Halted,
+    //输入主题中不应出现的消息类型
+    UnexpectedLogType,
+    //撤单的订单不在订单簿中：已全部成交、已撤销或订单号错误
+    UnknownOrder,
+    //同一用户的客户端订单号仍在去重窗口中，通常是同一订单被重复投递
+    DuplicateClientOrderId,
+}
+
+impl RejectReason {
+    /// 不含具体数值的原因标识，用作指标标签
+    pub fn label(&self) -> &'static str {
+        match self {
+            RejectReason::InvalidQuantity(_) => "invalid_quantity",
+            RejectReason::InvalidPrice(_) => "invalid_price",
+            RejectReason::MarketBuyDisabled => "market_buy_disabled",
+            RejectReason::MarketSellDisabled => "market_sell_disabled",
+            RejectReason::PriceScale { .. } => "price_scale",
+            RejectReason::QuantityScale { .. } => "quantity_scale",
+            RejectReason::TurnoverBelowMinimum(_) => "turnover_below_minimum",
+            RejectReason::QuantityBelowMinimum(_) => "quantity_below_minimum",
+            RejectReason::QuantityAboveMaximum(_) => "quantity_above_maximum",
+            RejectReason::DuplicateOrderId => "duplicate_order_id",
+            RejectReason::Halted => "halted",
+            RejectReason::UnexpectedLogType => "unexpected_log_type",
+            RejectReason::UnknownOrder => "unknown_order",
+            RejectReason::DuplicateClientOrderId => "duplicate_client_order_id",
+        }
+    }
+}
+
+impl fmt::Display for RejectReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RejectReason::InvalidQuantity(quantity) => write!(f, "invalid quantity {}", quantity),
+            RejectReason::InvalidPrice(price) => write!(f, "invalid price {}", price),
+            RejectReason::MarketBuyDisabled => write!(f, "market buy disabled"),
+            RejectReason::MarketSellDisabled => write!(f, "market sell disabled"),
+            RejectReason::PriceScale { price, scale } => write!(f, "price {} exceeds scale {}", price, scale),
+            RejectReason::QuantityScale { quantity, scale } => write!(f, "quantity {} exceeds scale {}", quantity, scale),
+            RejectReason::TurnoverBelowMinimum(min_turnover) => write!(f, "turnover below minimum {}", min_turnover),
+            RejectReason::QuantityBelowMinimum(min_quantity) => write!(f, "quantity below minimum {}", min_quantity),
+            RejectReason::QuantityAboveMaximum(max_quantity) => write!(f, "quantity above maximum {}", max_quantity),
+            RejectReason::DuplicateOrderId => write!(f, "duplicate order id"),
+            RejectReason::Halted => write!(f, "symbol is halted"),
+            RejectReason::UnexpectedLogType => write!(f, "unexpected log type"),
+            RejectReason::UnknownOrder => write!(f, "order not in order book"),
+            RejectReason::DuplicateClientOrderId => write!(f, "duplicate client order id"),
+        }
+    }
+}
+
 // 订单结构体
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Order {
@@ -51,6 +160,10 @@ pub struct Order {
     pub timestamp: u64,  // 毫秒级时间戳
     pub order_type: OrderType,
     pub side: Side,
+    #[serde(default)]
+    pub client_order_id: Option<String>,  // 客户端订单号，随执行回报带回
+    #[serde(default)]
+    pub filled_quantity: f64,             // 已成交数量，只用于订单簿中的挂单，新订单中忽略
 }
 
 // 假设 OrderType 和 Side 已实现 Display 特征
@@ -80,58 +193,57 @@ impl Order {
             timestamp: current_timestamp(),
             order_type,
             side,
+            client_order_id: None,
+            filled_quantity: 0.0,
         }
     }
 
     /// 从 FlatBuffers 数据解析出 Order 实例
     pub fn parse_order_flatbuffer(data: &[u8]) -> Result<Self, &'static str> {
         let fbs_order = flatbuffers::root::<FbsOrder>(data).map_err(|_| "Failed to parse FlatBuffer data as Order")?;
+        Self::from_flatbuffer(&fbs_order)
+    }
 
-        // 将 FlatBuffers 数据转换成 Rust 的 Order 实例
+    /// 将 FlatBuffers 中的 Order 表转换成 Rust 的 Order 实例，也用于嵌套在 SpotLog 中的订单
+    pub fn from_flatbuffer(fbs_order: &FbsOrder) -> Result<Self, &'static str> {
         Ok(Order {
             id: fbs_order.id(),
             user_id: fbs_order.user_id(),
             price: fbs_order.price(),
             quantity: fbs_order.quantity(),
             timestamp: fbs_order.timestamp(),
-            order_type: match fbs_order.order_type() {
-                FbsOrderType::Limit => OrderType::Limit,
-                FbsOrderType::Market => OrderType::Market,
-                _ => return Err("Invalid order type in FlatBuffer data"),
-            },
-            side: match fbs_order.side() {
-                FbsSide::Buy => Side::Buy,
-                FbsSide::Sell => Side::Sell,
-                _ => return Err("Invalid side in FlatBuffer data"),
-            },
+            order_type: fbs_order.order_type().try_into()?,
+            side: fbs_order.side().try_into()?,
+            client_order_id: fbs_order.client_order_id().map(str::to_string),
+            filled_quantity: fbs_order.filled_quantity(),
         })
     }
 
     /// 将 Order 实例序列化为 FlatBuffers 格式
     pub fn to_flatbuffer(&self) -> Vec<u8> {
         let mut builder = FlatBufferBuilder::with_capacity(1024);
+        let order = self.create_flatbuffer(&mut builder);
+        builder.finish(order, None);
+
+        builder.finished_data().to_vec()
+    }
 
-        let order = FbsOrder::create(
-            &mut builder,
+    /// 在 builder 中写入 Order 表，返回其偏移，供外层表引用
+    pub fn create_flatbuffer<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<FbsOrder<'a>> {
+        let client_order_id = self.client_order_id.as_deref().map(|client_order_id| builder.create_string(client_order_id));
+        FbsOrder::create(
+            builder,
             &OrderArgs {
                 id: self.id,
                 user_id: self.user_id,
                 price: self.price,
                 quantity: self.quantity,
                 timestamp: self.timestamp,
-                order_type: match self.order_type {
-                    OrderType::Limit => FbsOrderType::Limit,
-                    OrderType::Market => FbsOrderType::Market,
-                },
-                side: match self.side {
-                    Side::Buy => FbsSide::Buy,
-                    Side::Sell => FbsSide::Sell,
-                },
+                order_type: self.order_type.into(),
+                side: self.side.into(),
+                client_order_id,
+                filled_quantity: self.filled_quantity,
             },
-        );
-
-        builder.finish(order, None);
-
-        builder.finished_data().to_vec()
+        )
     }
 }
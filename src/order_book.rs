@@ -1,19 +1,82 @@
 use std::cmp::Reverse;
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use ordered_float::OrderedFloat;
+use serde::{Deserialize, Serialize};
 use tklog::async_info;
-use crate::date::current_timestamp;
+use crate::execution::{ExecutionReport, ExecutionStatus};
 use crate::order::{Order, OrderType, Side};
 use crate::spot_log::{LogType, SpotLog};
 use crate::trade::Trade;
 
 
+// 聚合后的价格档位
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceLevel {
+    pub price: f64,
+    pub quantity: f64,     // 该价格上所有挂单的剩余数量之和
+    pub order_count: usize,
+}
+
+// 买卖双方前 N 档的聚合深度
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Depth {
+    pub bids: Vec<PriceLevel>,   // 价格从高到低
+    pub asks: Vec<PriceLevel>,   // 价格从低到高
+}
+
+// 订单簿逐笔变化类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BookEventKind {
+    //新挂单，追加到价格档位队尾
+    Add,
+    //部分撤单，数量为撤销的部分
+    Reduce,
+    //被动成交，数量为成交数量，剩余为 0 时订单离开订单簿
+    Execute,
+    //撤单，数量为撤销前的剩余数量
+    Delete,
+}
+
+// 订单簿的一次逐笔变化，按发生顺序应用即可重建包括排队顺序在内的完整订单簿
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookEvent {
+    pub kind: BookEventKind,
+    pub order_id: u64,
+    pub side: Side,
+    pub price: f64,
+    pub quantity: f64,
+}
+
+// 一笔成交后订单的执行回报，`remaining_quantity` 为成交后的剩余数量
+fn fill_report(order: &Order, remaining_quantity: f64, quantity: f64, price: f64) -> ExecutionReport {
+    let status = if remaining_quantity > 0.0 { ExecutionStatus::PartiallyFilled } else { ExecutionStatus::Filled };
+    ExecutionReport::new(order, status, remaining_quantity).with_last(quantity, price)
+}
+
+fn aggregate_level(price: f64, orders: &[Order]) -> PriceLevel {
+    PriceLevel {
+        price,
+        quantity: orders.iter().map(|o| o.quantity).sum(),
+        order_count: orders.len(),
+    }
+}
+
 // 订单簿结构体
+#[derive(Default)]
 pub struct OrderBook {
     // 买单：按价格降序排列
     bids: BTreeMap<Reverse<OrderedFloat<f64>>, Vec<Order>>,
     // 卖单：按价格升序排列
     asks: BTreeMap<OrderedFloat<f64>, Vec<Order>>,
+    // 上次取出深度变化后数量发生变化的价格档位
+    changed_bids: BTreeSet<OrderedFloat<f64>>,
+    changed_asks: BTreeSet<OrderedFloat<f64>>,
+    // 挂单 ID -> (方向, 价格)，用于撤单时定位订单
+    order_index: HashMap<u64, (Side, OrderedFloat<f64>)>,
+    // 上次取出后产生的逐笔变化
+    book_events: Vec<BookEvent>,
+    // 上次取出后产生的执行回报
+    execution_reports: Vec<ExecutionReport>,
 }
 
 impl OrderBook {
@@ -21,13 +84,19 @@ impl OrderBook {
         OrderBook {
             bids: BTreeMap::new(),
             asks: BTreeMap::new(),
+            changed_bids: BTreeSet::new(),
+            changed_asks: BTreeSet::new(),
+            order_index: HashMap::new(),
+            book_events: Vec::new(),
+            execution_reports: Vec::new(),
         }
     }
 
     // 添加订单并执行撮合
-    pub fn add_order(&mut self, order: Order) -> Vec<SpotLog> {
+    pub fn add_order(&mut self, mut order: Order) -> Vec<SpotLog> {
         let mut spot_log = Vec::new();
         let mut remaining_quantity = order.quantity;
+        order.filled_quantity = 0.0;
 
         match order.side {
             Side::Buy => {
@@ -47,6 +116,7 @@ impl OrderBook {
 
                     // Use a separate block to limit the mutable borrow scope
                     if let Some(orders_at_price) = self.asks.get_mut(&price) {
+                        self.changed_asks.insert(price);
                         // Use a separate mutable borrow for the orders vector
                         let mut i = 0;
                         while i < orders_at_price.len() && remaining_quantity > 0.0 {
@@ -59,7 +129,8 @@ impl OrderBook {
                                 sell_order_id: sell_order.id,
                                 price: trade_price,
                                 quantity: trade_quantity,
-                                timestamp: current_timestamp(),
+                                // 成交时间取自主动方订单，保证同一输入流在主备节点上产生完全一致的结果
+                                timestamp: order.timestamp,
                             };
 
                             spot_log.push(SpotLog {
@@ -67,6 +138,8 @@ impl OrderBook {
                                 seq_id: 1,
                                 order: None,
                                 trade: Some(trade.clone()), // Clone to move into the async task
+                                admin: None,
+                                report: None,
                             });
 
                             let order_id = order.id;
@@ -80,9 +153,21 @@ impl OrderBook {
 
                             remaining_quantity -= trade_quantity;
                             sell_order.quantity -= trade_quantity;
+                            self.book_events.push(BookEvent {
+                                kind: BookEventKind::Execute,
+                                order_id: sell_order_id,
+                                side: Side::Sell,
+                                price: trade_price,
+                                quantity: trade_quantity,
+                            });
+                            order.filled_quantity += trade_quantity;
+                            sell_order.filled_quantity += trade_quantity;
+                            self.execution_reports.push(fill_report(&order, remaining_quantity, trade_quantity, trade_price));
+                            self.execution_reports.push(fill_report(sell_order, sell_order.quantity, trade_quantity, trade_price));
 
                             if sell_order.quantity <= 0.0 {
                                 // Remove the sell order if fully matched
+                                self.order_index.remove(&sell_order_id);
                                 orders_at_price.remove(i);
                             } else {
                                 i += 1;
@@ -115,9 +200,19 @@ impl OrderBook {
                         );
                     });
 
+                    self.changed_bids.insert(OrderedFloat::from(buy_order.price));
+                    self.order_index.insert(buy_order.id, (Side::Buy, OrderedFloat::from(buy_order.price)));
+                    self.book_events.push(BookEvent {
+                        kind: BookEventKind::Add,
+                        order_id: buy_order.id,
+                        side: Side::Buy,
+                        price: buy_order.price,
+                        quantity: buy_order.quantity,
+                    });
+                    self.execution_reports.push(ExecutionReport::new(&buy_order, ExecutionStatus::Resting, buy_order.quantity));
                     self.bids
                         .entry(Reverse(OrderedFloat::from(buy_order.price)))
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(buy_order);
                 }
             }
@@ -140,6 +235,7 @@ impl OrderBook {
                     }
 
                     if let Some(orders_at_price) = self.bids.get_mut(&reverse_price) {
+                        self.changed_bids.insert(reverse_price.0);
                         let mut i = 0;
                         while i < orders_at_price.len() && remaining_quantity > 0.0 {
                             let buy_order = &mut orders_at_price[i];
@@ -151,7 +247,8 @@ impl OrderBook {
                                 sell_order_id: order.id,
                                 price: trade_price,
                                 quantity: trade_quantity,
-                                timestamp: current_timestamp(),
+                                // 成交时间取自主动方订单，保证同一输入流在主备节点上产生完全一致的结果
+                                timestamp: order.timestamp,
                             };
 
                             spot_log.push(SpotLog {
@@ -159,6 +256,8 @@ impl OrderBook {
                                 seq_id: 1,
                                 order: None,
                                 trade: Some(trade.clone()),
+                                admin: None,
+                                report: None,
                             });
 
                             let order_id = order.id;
@@ -172,9 +271,21 @@ impl OrderBook {
 
                             remaining_quantity -= trade_quantity;
                             buy_order.quantity -= trade_quantity;
+                            self.book_events.push(BookEvent {
+                                kind: BookEventKind::Execute,
+                                order_id: buy_order_id,
+                                side: Side::Buy,
+                                price: trade_price,
+                                quantity: trade_quantity,
+                            });
+                            order.filled_quantity += trade_quantity;
+                            buy_order.filled_quantity += trade_quantity;
+                            self.execution_reports.push(fill_report(&order, remaining_quantity, trade_quantity, trade_price));
+                            self.execution_reports.push(fill_report(buy_order, buy_order.quantity, trade_quantity, trade_price));
 
                             if buy_order.quantity <= 0.0 {
                                 // Remove the buy order if fully matched
+                                self.order_index.remove(&buy_order_id);
                                 orders_at_price.remove(i);
                             } else {
                                 i += 1;
@@ -207,19 +318,237 @@ impl OrderBook {
                         );
                     });
 
+                    self.changed_asks.insert(OrderedFloat::from(sell_order.price));
+                    self.order_index.insert(sell_order.id, (Side::Sell, OrderedFloat::from(sell_order.price)));
+                    self.book_events.push(BookEvent {
+                        kind: BookEventKind::Add,
+                        order_id: sell_order.id,
+                        side: Side::Sell,
+                        price: sell_order.price,
+                        quantity: sell_order.quantity,
+                    });
+                    self.execution_reports.push(ExecutionReport::new(&sell_order, ExecutionStatus::Resting, sell_order.quantity));
                     self.asks
                         .entry(OrderedFloat::from(sell_order.price))
-                        .or_insert_with(Vec::new)
+                        .or_default()
                         .push(sell_order);
                 }
             }
         }
 
+        // 市价单不挂单，未成交的部分失效
+        if remaining_quantity > 0.0 && order.order_type == OrderType::Market {
+            self.execution_reports.push(ExecutionReport::new(&order, ExecutionStatus::Expired, 0.0).with_last(remaining_quantity, 0.0));
+        }
+
         spot_log
     }
 
+    /// 撤销挂单。`quantity` 大于 0 且小于剩余数量时只撤销这部分，否则撤销全部剩余数量；
+    /// 订单不在订单簿中时不产生结果
+    pub fn cancel_order(&mut self, order_id: u64, quantity: f64) -> Vec<SpotLog> {
+        let Some(&(side, price)) = self.order_index.get(&order_id) else {
+            return Vec::new();
+        };
+        let orders_at_price = match side {
+            Side::Buy => self.bids.get_mut(&Reverse(price)),
+            Side::Sell => self.asks.get_mut(&price),
+        };
+        let Some(orders_at_price) = orders_at_price else {
+            return Vec::new();
+        };
+        let Some(i) = orders_at_price.iter().position(|o| o.id == order_id) else {
+            return Vec::new();
+        };
+
+        let remaining_quantity = orders_at_price[i].quantity;
+        let (kind, cancelled_quantity, order) = if quantity > 0.0 && quantity < remaining_quantity {
+            orders_at_price[i].quantity -= quantity;
+            (BookEventKind::Reduce, quantity, orders_at_price[i].clone())
+        } else {
+            self.order_index.remove(&order_id);
+            (BookEventKind::Delete, remaining_quantity, orders_at_price.remove(i))
+        };
+
+        if orders_at_price.is_empty() {
+            match side {
+                Side::Buy => self.bids.remove(&Reverse(price)),
+                Side::Sell => self.asks.remove(&price),
+            };
+        }
+        match side {
+            Side::Buy => self.changed_bids.insert(price),
+            Side::Sell => self.changed_asks.insert(price),
+        };
+        self.book_events.push(BookEvent {
+            kind,
+            order_id,
+            side,
+            price: price.into_inner(),
+            quantity: cancelled_quantity,
+        });
+
+        self.execution_reports.push(
+            ExecutionReport::new(&order, ExecutionStatus::Cancelled, remaining_quantity - cancelled_quantity)
+                .with_last(cancelled_quantity, 0.0),
+        );
+
+        tokio::spawn(async move {
+            async_info!(format!("Cancelled Order ID: {}, Quantity: {:.2}", order_id, cancelled_quantity));
+        });
+
+        vec![SpotLog {
+            log_type: LogType::CancelOrder,
+            seq_id: 1,
+            order: Some(Order { quantity: cancelled_quantity, ..order }),
+            trade: None,
+            admin: None,
+            report: None,
+        }]
+    }
+
+    /// 撤销全部挂单，指定 `user_id` 时只撤销该用户的挂单，按价格优先、时间优先的顺序撤销
+    pub fn cancel_all(&mut self, user_id: Option<u64>) -> Vec<SpotLog> {
+        let order_ids: Vec<u64> = self.bids.values().flatten()
+            .chain(self.asks.values().flatten())
+            .filter(|order| user_id.is_none_or(|user_id| order.user_id == user_id))
+            .map(|order| order.id)
+            .collect();
+        order_ids.into_iter()
+            .flat_map(|order_id| self.cancel_order(order_id, 0.0))
+            .collect()
+    }
+
+    /// 取出上次调用以来产生的逐笔变化
+    pub fn take_book_events(&mut self) -> Vec<BookEvent> {
+        std::mem::take(&mut self.book_events)
+    }
+
+    /// 取出上次调用以来产生的执行回报：每笔成交主动方和被动方各一条，
+    /// 以及挂单、市价单失效和撤单。时间取自订单，由调用方统一为输入消息的时间
+    pub fn take_execution_reports(&mut self) -> Vec<ExecutionReport> {
+        std::mem::take(&mut self.execution_reports)
+    }
+
+    /// 订单是否仍挂在订单簿中
+    pub fn contains(&self, order_id: u64) -> bool {
+        self.order_index.contains_key(&order_id)
+    }
+
+    /// 挂单总数
     pub fn len(&self) -> usize {
-        self.bids.len() + self.asks.len()
+        self.bids.values().map(Vec::len).sum::<usize>() + self.asks.values().map(Vec::len).sum::<usize>()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bids.is_empty() && self.asks.is_empty()
+    }
+
+    /// 买卖双方的价格档位数
+    pub fn level_count(&self) -> (usize, usize) {
+        (self.bids.len(), self.asks.len())
+    }
+
+    /// 买卖双方前 `limit` 档的聚合深度
+    pub fn depth(&self, limit: usize) -> Depth {
+        Depth {
+            bids: self.bids.iter()
+                .take(limit)
+                .map(|(Reverse(OrderedFloat(price)), orders)| aggregate_level(*price, orders))
+                .collect(),
+            asks: self.asks.iter()
+                .take(limit)
+                .map(|(OrderedFloat(price), orders)| aggregate_level(*price, orders))
+                .collect(),
+        }
+    }
+
+    /// 指定方向、指定价格上的聚合档位，价格上没有挂单时返回 None
+    pub fn level(&self, side: Side, price: f64) -> Option<PriceLevel> {
+        let orders = match side {
+            Side::Buy => self.bids.get(&Reverse(OrderedFloat(price))),
+            Side::Sell => self.asks.get(&OrderedFloat(price)),
+        };
+        orders.map(|orders| aggregate_level(price, orders))
+    }
+
+    /// 取出上次调用以来发生变化的档位及其最新聚合数量，已被移除的档位数量和订单数为 0
+    pub fn take_depth_changes(&mut self) -> Depth {
+        let changed_bids = std::mem::take(&mut self.changed_bids);
+        let changed_asks = std::mem::take(&mut self.changed_asks);
+        let removed = |price: f64| PriceLevel { price, quantity: 0.0, order_count: 0 };
+
+        Depth {
+            bids: changed_bids.into_iter()
+                .rev()
+                .map(|OrderedFloat(price)| self.level(Side::Buy, price).unwrap_or_else(|| removed(price)))
+                .collect(),
+            asks: changed_asks.into_iter()
+                .map(|OrderedFloat(price)| self.level(Side::Sell, price).unwrap_or_else(|| removed(price)))
+                .collect(),
+        }
+    }
+
+    /// 最优买价
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.keys().next().map(|Reverse(OrderedFloat(price))| *price)
+    }
+
+    /// 最优卖价
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.keys().next().map(|OrderedFloat(price)| *price)
+    }
+
+    /// 买卖价差，任一方为空时返回 None
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    /// 中间价，任一方为空时返回 None
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_ask()? + self.best_bid()?) / 2.0)
+    }
+
+    /// 买方挂单剩余数量之和
+    pub fn bid_volume(&self) -> f64 {
+        self.bids.values().flatten().map(|o| o.quantity).sum()
+    }
+
+    /// 卖方挂单剩余数量之和
+    pub fn ask_volume(&self) -> f64 {
+        self.asks.values().flatten().map(|o| o.quantity).sum()
+    }
+
+    /// 买卖双方挂单剩余数量之和
+    pub fn total_volume(&self) -> f64 {
+        self.bid_volume() + self.ask_volume()
+    }
+
+    /// 按撮合优先级导出买卖双方的全部挂单，用于生成快照
+    pub fn resting_orders(&self) -> (Vec<Order>, Vec<Order>) {
+        let bids = self.bids.values().flatten().cloned().collect();
+        let asks = self.asks.values().flatten().cloned().collect();
+        (bids, asks)
+    }
+
+    /// 从快照中的挂单恢复订单簿，传入的订单需已按撮合优先级排列
+    pub fn restore(bids: Vec<Order>, asks: Vec<Order>) -> Self {
+        let mut order_book = OrderBook::new();
+        for order in bids {
+            order_book.order_index.insert(order.id, (Side::Buy, OrderedFloat::from(order.price)));
+            order_book.bids
+                .entry(Reverse(OrderedFloat::from(order.price)))
+                .or_default()
+                .push(order);
+        }
+        for order in asks {
+            order_book.order_index.insert(order.id, (Side::Sell, OrderedFloat::from(order.price)));
+            order_book.asks
+                .entry(OrderedFloat::from(order.price))
+                .or_default()
+                .push(order);
+        }
+        order_book
     }
 
     pub async fn print_order_book(&self) {
@@ -245,3 +574,139 @@ impl OrderBook {
         async_info!("====================\n");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(side: Side, price: f64, quantity: f64) -> Order {
+        Order::new(1, price, quantity, OrderType::Limit, side)
+    }
+
+    #[tokio::test]
+    async fn test_depth_aggregates_levels() {
+        let mut order_book = OrderBook::new();
+        order_book.add_order(limit(Side::Buy, 99.0, 1.0));
+        order_book.add_order(limit(Side::Buy, 100.0, 2.0));
+        order_book.add_order(limit(Side::Buy, 100.0, 3.0));
+        order_book.add_order(limit(Side::Sell, 101.0, 4.0));
+        order_book.add_order(limit(Side::Sell, 103.0, 5.0));
+
+        let depth = order_book.depth(1);
+        assert_eq!(depth.bids, vec![PriceLevel { price: 100.0, quantity: 5.0, order_count: 2 }]);
+        assert_eq!(depth.asks, vec![PriceLevel { price: 101.0, quantity: 4.0, order_count: 1 }]);
+
+        assert_eq!(order_book.len(), 5);
+        assert_eq!(order_book.level_count(), (2, 2));
+        assert_eq!(order_book.best_bid(), Some(100.0));
+        assert_eq!(order_book.best_ask(), Some(101.0));
+        assert_eq!(order_book.spread(), Some(1.0));
+        assert_eq!(order_book.mid_price(), Some(100.5));
+        assert_eq!(order_book.total_volume(), 15.0);
+
+        // 吃掉 101 档后，最优卖价移动到 103
+        order_book.add_order(limit(Side::Buy, 101.0, 4.0));
+        assert_eq!(order_book.best_ask(), Some(103.0));
+        assert_eq!(order_book.level(Side::Sell, 101.0), None);
+    }
+
+    #[tokio::test]
+    async fn test_take_depth_changes() {
+        let mut order_book = OrderBook::new();
+        order_book.add_order(limit(Side::Sell, 101.0, 1.0));
+        order_book.add_order(limit(Side::Sell, 102.0, 2.0));
+        order_book.add_order(limit(Side::Buy, 99.0, 3.0));
+        order_book.add_order(limit(Side::Buy, 100.0, 4.0));
+
+        let changes = order_book.take_depth_changes();
+        assert_eq!(changes, order_book.depth(usize::MAX));
+        assert_eq!(order_book.take_depth_changes(), Depth::default());
+
+        // 吃掉 101 整档和 102 的一部分，剩余部分挂在 102 买方
+        order_book.add_order(limit(Side::Buy, 102.0, 4.0));
+        let changes = order_book.take_depth_changes();
+        assert_eq!(changes.asks, vec![
+            PriceLevel { price: 101.0, quantity: 0.0, order_count: 0 },
+            PriceLevel { price: 102.0, quantity: 0.0, order_count: 0 },
+        ]);
+        assert_eq!(changes.bids, vec![PriceLevel { price: 102.0, quantity: 1.0, order_count: 1 }]);
+    }
+
+    #[tokio::test]
+    async fn test_book_events_rebuild_queue() {
+        let mut order_book = OrderBook::new();
+        let first = limit(Side::Sell, 101.0, 1.0);
+        let second = limit(Side::Sell, 101.0, 2.0);
+        let third = limit(Side::Sell, 101.0, 3.0);
+        let (first_id, second_id, third_id) = (first.id, second.id, third.id);
+        order_book.add_order(first);
+        order_book.add_order(second);
+        order_book.add_order(third);
+
+        // 部分撤销队首，撤掉队中，再成交队首及队尾的一部分
+        assert_eq!(order_book.cancel_order(first_id, 0.5)[0].order.as_ref().unwrap().quantity, 0.5);
+        assert_eq!(order_book.cancel_order(second_id, 0.0)[0].order.as_ref().unwrap().quantity, 2.0);
+        assert!(order_book.cancel_order(second_id, 0.0).is_empty());
+        order_book.add_order(limit(Side::Buy, 101.0, 1.5));
+
+        let kinds: Vec<_> = order_book.take_book_events().iter().map(|e| (e.kind, e.order_id, e.quantity)).collect();
+        assert_eq!(kinds, vec![
+            (BookEventKind::Add, first_id, 1.0),
+            (BookEventKind::Add, second_id, 2.0),
+            (BookEventKind::Add, third_id, 3.0),
+            (BookEventKind::Reduce, first_id, 0.5),
+            (BookEventKind::Delete, second_id, 2.0),
+            (BookEventKind::Execute, first_id, 0.5),
+            (BookEventKind::Execute, third_id, 1.0),
+        ]);
+        assert_eq!(order_book.level(Side::Sell, 101.0), Some(PriceLevel { price: 101.0, quantity: 2.0, order_count: 1 }));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all() {
+        let mut order_book = OrderBook::new();
+        order_book.add_order(limit(Side::Buy, 99.0, 1.0));
+        order_book.add_order(Order::new(2, 100.0, 2.0, OrderType::Limit, Side::Buy));
+        order_book.add_order(Order::new(2, 101.0, 3.0, OrderType::Limit, Side::Sell));
+
+        let cancelled = order_book.cancel_all(Some(2));
+        assert_eq!(cancelled.len(), 2);
+        assert!(cancelled.iter().all(|log| log.log_type == LogType::CancelOrder));
+        assert_eq!(order_book.len(), 1);
+
+        assert_eq!(order_book.cancel_all(None).len(), 1);
+        assert_eq!(order_book.len(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_execution_reports() {
+        let mut order_book = OrderBook::new();
+        let maker = Order { client_order_id: Some("maker-1".to_string()), ..limit(Side::Sell, 101.0, 3.0) };
+        let taker = limit(Side::Buy, 101.0, 1.0);
+        let market = Order::new(1, 0.0, 4.0, OrderType::Market, Side::Buy);
+        let resting = limit(Side::Sell, 102.0, 1.0);
+        let (maker_id, taker_id, market_id, resting_id) = (maker.id, taker.id, market.id, resting.id);
+        order_book.add_order(maker);
+        order_book.add_order(taker);
+        order_book.add_order(market);
+        order_book.add_order(resting);
+        order_book.cancel_order(resting_id, 0.0);
+
+        let reports = order_book.take_execution_reports();
+        assert!(reports.iter().filter(|r| r.order_id == maker_id).all(|r| r.client_order_id.as_deref() == Some("maker-1")));
+        let reports: Vec<_> = reports.into_iter()
+            .map(|r| (r.order_id, r.status, r.last_quantity, r.cumulative_quantity, r.remaining_quantity))
+            .collect();
+        assert_eq!(reports, vec![
+            (maker_id, ExecutionStatus::Resting, 0.0, 0.0, 3.0),
+            (taker_id, ExecutionStatus::Filled, 1.0, 1.0, 0.0),
+            (maker_id, ExecutionStatus::PartiallyFilled, 1.0, 1.0, 2.0),
+            // 市价单吃掉剩余的 2，其余 2 失效
+            (market_id, ExecutionStatus::PartiallyFilled, 2.0, 2.0, 2.0),
+            (maker_id, ExecutionStatus::Filled, 2.0, 3.0, 0.0),
+            (market_id, ExecutionStatus::Expired, 2.0, 2.0, 0.0),
+            (resting_id, ExecutionStatus::Resting, 0.0, 0.0, 1.0),
+            (resting_id, ExecutionStatus::Cancelled, 1.0, 0.0, 0.0),
+        ]);
+    }
+}
@@ -0,0 +1,376 @@
+use std::collections::HashSet;
+use std::io;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use tklog::{async_error, async_info};
+use tokio::io::AsyncWriteExt;
+use tokio::sync::mpsc::{self, error::TrySendError};
+use tokio::sync::{Mutex, Notify};
+use tokio::time::Instant;
+use crate::db_pool::get_database;
+use crate::model::exchange_order::ExchangeOrder;
+use crate::model::spot_trade::SpotTrade;
+use crate::order::{Order, OrderType};
+use crate::order_book::{BookEvent, BookEventKind};
+use crate::spot_log::SpotLog;
+use crate::trade::Trade;
+
+// 写入失败后重试的最长间隔
+const MAX_RETRY_BACKOFF: Duration = Duration::from_secs(5);
+
+// 需要写入数据库的撮合事件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PersistEvent {
+    //一笔成交，买卖双方订单累加成交量
+    Trade { seq_id: u64, trade: Trade },
+    //订单剩余部分被撤销：撤单或市价单未成交的部分
+    Cancel { order_id: String, timestamp: i64 },
+}
+
+impl PersistEvent {
+    /// 一条输入消息产生的待写入事件，`results` 须已分配序列号
+    pub fn collect(
+        timestamp: u64,
+        incoming: Option<&Order>,
+        results: &[SpotLog],
+        book_events: &[BookEvent],
+    ) -> Vec<PersistEvent> {
+        let cancel = |order_id: u64| PersistEvent::Cancel { order_id: order_id.to_string(), timestamp: timestamp as i64 };
+        let mut events: Vec<PersistEvent> = results.iter()
+            .filter_map(|result| result.trade.clone().map(|trade| PersistEvent::Trade { seq_id: result.seq_id, trade }))
+            .collect();
+        // 部分撤单后订单仍在交易中，只有整单离开订单簿的撤单需要更新订单状态
+        events.extend(book_events.iter()
+            .filter(|event| event.kind == BookEventKind::Delete)
+            .map(|event| cancel(event.order_id)));
+        // 市价单不挂单，撮合后未成交的部分即被撤销，已完全成交的订单不受影响
+        if let Some(order) = incoming.filter(|order| order.order_type == OrderType::Market) {
+            events.push(cancel(order.id));
+        }
+        events
+    }
+}
+
+// 一个事务内写入的事件。同一订单的成交总是先于其撤销发生，因此先写成交再写撤销
+#[derive(Debug, Default, PartialEq)]
+struct Batch {
+    trades: Vec<SpotTrade>,
+    cancels: Vec<(String, i64)>,
+}
+
+impl Batch {
+    // 价格或数量无法转换为 Decimal 的成交不写入，连同错误一起返回，由调用方记录
+    fn new(symbol: &str, events: Vec<PersistEvent>) -> (Self, Vec<(u64, Trade, rust_decimal::Error)>) {
+        let mut batch = Batch::default();
+        let mut invalid = Vec::new();
+        for event in events {
+            match event {
+                PersistEvent::Trade { seq_id, trade } => match SpotTrade::new(symbol, seq_id, &trade) {
+                    Ok(spot_trade) => batch.trades.push(spot_trade),
+                    Err(e) => invalid.push((seq_id, trade, e)),
+                },
+                PersistEvent::Cancel { order_id, timestamp } => batch.cancels.push((order_id, timestamp)),
+            }
+        }
+        (batch, invalid)
+    }
+
+    // 成交表以 (交易对, 序列号) 去重，只有本次新写入的成交才累加到订单上，整批在同一事务中提交，
+    // 重放或重试整批写入都不会重复累加
+    async fn write(&self) -> Result<(), sqlx::Error> {
+        let mut tx = get_database()?.begin().await?;
+        let inserted = SpotTrade::insert_batch(&mut tx, &self.trades).await?;
+        for trade in &self.trades {
+            if !inserted.contains(&(trade.symbol.clone(), trade.seq_id)) {
+                continue;
+            }
+            let turnover = trade.turnover();
+            ExchangeOrder::apply_trade(&mut tx, &trade.buy_order_id, trade.quantity, turnover, trade.timestamp).await?;
+            ExchangeOrder::apply_trade(&mut tx, &trade.sell_order_id, trade.quantity, turnover, trade.timestamp).await?;
+        }
+        let mut cancelled = HashSet::new();
+        for (order_id, timestamp) in &self.cancels {
+            if cancelled.insert(order_id) {
+                ExchangeOrder::cancel(&mut tx, order_id, *timestamp).await?;
+            }
+        }
+        tx.commit().await
+    }
+}
+
+// 队列溢出时的落盘文件，每行一条 JSON 格式的事件。溢出期间的事件全部按顺序追加到文件中，
+// 写入任务处理完队列后再写入文件中的事件，同一订单的成交仍先于其撤销写入
+#[derive(Debug)]
+struct Spill {
+    path: PathBuf,      // 正在追加的文件
+    draining: PathBuf,  // 写入任务正在处理的文件，写完后删除，进程退出时未写完的下次启动时继续
+    active: AtomicBool,
+    appended: Notify,   // 溢出期间事件不进入队列，由此唤醒等待队列的写入任务
+    lock: Mutex<()>,
+}
+
+impl Spill {
+    fn new(dir: &str, symbol: &str) -> Self {
+        let name = symbol.replace('/', "_");
+        let path = PathBuf::from(dir).join(format!("{}.persist", name));
+        let draining = PathBuf::from(dir).join(format!("{}.persist.draining", name));
+        // 上次退出时未写完的事件
+        let active = path.exists() || draining.exists();
+        Spill { path, draining, active: AtomicBool::new(active), appended: Notify::new(), lock: Mutex::new(()) }
+    }
+
+    fn is_active(&self) -> bool {
+        self.active.load(Ordering::Acquire)
+    }
+
+    async fn append(&self, events: &[PersistEvent]) -> io::Result<()> {
+        let _guard = self.lock.lock().await;
+        self.active.store(true, Ordering::Release);
+        if let Some(dir) = self.path.parent() {
+            tokio::fs::create_dir_all(dir).await?;
+        }
+        let mut lines = Vec::new();
+        for event in events {
+            serde_json::to_writer(&mut lines, event)?;
+            lines.push(b'\n');
+        }
+        let mut file = tokio::fs::OpenOptions::new().create(true).append(true).open(&self.path).await?;
+        file.write_all(&lines).await?;
+        file.flush().await?;
+        self.appended.notify_one();
+        Ok(())
+    }
+
+    // 取出待写入的事件：先取上次未写完的文件，否则将正在追加的文件转为待处理。
+    // 没有待写入的事件时结束溢出状态，之后的事件重新进入队列
+    async fn take(&self) -> io::Result<Option<Vec<PersistEvent>>> {
+        let _guard = self.lock.lock().await;
+        if !self.draining.exists() {
+            if !self.path.exists() {
+                self.active.store(false, Ordering::Release);
+                return Ok(None);
+            }
+            tokio::fs::rename(&self.path, &self.draining).await?;
+        }
+        let content = tokio::fs::read(&self.draining).await?;
+        let mut events = Vec::new();
+        for line in content.split(|byte| *byte == b'\n').filter(|line| !line.is_empty()) {
+            events.push(serde_json::from_slice(line)?);
+        }
+        Ok(Some(events))
+    }
+
+    async fn finish_draining(&self) -> io::Result<()> {
+        tokio::fs::remove_file(&self.draining).await
+    }
+}
+
+/// 成交及订单状态的异步写入器，写入在独立任务中按批进行，不阻塞撮合。
+/// 写入幂等，数据库不可用时持续重试，期间事件在队列中积压，队列占满后落盘，数据库恢复后按顺序补写
+#[derive(Debug, Clone)]
+pub struct PersistenceSink {
+    symbol: String,
+    sender: mpsc::Sender<PersistEvent>,
+    spill: Arc<Spill>,
+}
+
+impl PersistenceSink {
+    pub fn spawn(symbol: &str, spill_dir: &str, queue_size: usize, batch_size: usize, flush_interval: Duration) -> Self {
+        let (sender, mut receiver) = mpsc::channel::<PersistEvent>(queue_size);
+        let spill = Arc::new(Spill::new(spill_dir, symbol));
+        let writer = Writer { symbol: symbol.to_string(), batch_size: batch_size.max(1) };
+        let writer_spill = spill.clone();
+        tokio::spawn(async move {
+            let mut events = Vec::with_capacity(writer.batch_size);
+            loop {
+                // 队列中的事件早于落盘的事件，队列写完后再补写落盘的事件
+                if writer_spill.is_active() && receiver.is_empty() {
+                    writer.drain_spill(&writer_spill).await;
+                    continue;
+                }
+                tokio::select! {
+                    received = receiver.recv_many(&mut events, writer.batch_size) => if received == 0 {
+                        break;
+                    },
+                    _ = writer_spill.appended.notified() => continue,
+                }
+                // 凑批：最多等待 flush_interval
+                let deadline = Instant::now() + flush_interval;
+                while events.len() < writer.batch_size {
+                    let remaining = writer.batch_size - events.len();
+                    match tokio::time::timeout_at(deadline, receiver.recv_many(&mut events, remaining)).await {
+                        Ok(received) if received > 0 => continue,
+                        _ => break,
+                    }
+                }
+                writer.write(std::mem::take(&mut events)).await;
+            }
+        });
+        PersistenceSink { symbol: symbol.to_string(), sender, spill }
+    }
+
+    /// 放入写入队列，不等待：队列已满或正在落盘时追加到落盘文件
+    pub async fn write(&self, events: Vec<PersistEvent>) {
+        let mut overflow = Vec::new();
+        for event in events {
+            if !overflow.is_empty() || self.spill.is_active() {
+                overflow.push(event);
+                continue;
+            }
+            match self.sender.try_send(event) {
+                Ok(()) => {}
+                Err(TrySendError::Full(event)) => {
+                    async_error!(format!("[ALERT] Persistence queue of {} is full, spilling to {}", self.symbol, self.spill.path.display()));
+                    overflow.push(event);
+                }
+                Err(TrySendError::Closed(_)) => {
+                    async_error!(format!("Failed to queue event for persistence of {}: writer stopped", self.symbol));
+                    return;
+                }
+            }
+        }
+        if overflow.is_empty() {
+            return;
+        }
+        if let Err(e) = self.spill.append(&overflow).await {
+            async_error!(format!("[ALERT] Failed to spill {} persistence events of {}: {}", overflow.len(), self.symbol, e));
+        }
+    }
+}
+
+// 写入任务
+struct Writer {
+    symbol: String,
+    batch_size: usize,
+}
+
+impl Writer {
+    // 写入一批事件，失败时持续重试
+    async fn write(&self, events: Vec<PersistEvent>) {
+        let (batch, invalid) = Batch::new(&self.symbol, events);
+        for (seq_id, trade, e) in invalid {
+            async_error!(format!(
+                "[ALERT] Trade {} of {} not persisted, price {} or quantity {} is not a valid decimal: {}",
+                seq_id, self.symbol, trade.price, trade.quantity, e
+            ));
+        }
+        let mut attempt = 1;
+        while let Err(e) = batch.write().await {
+            async_error!(format!(
+                "Failed to persist {} trades and {} cancels of {} (attempt {}): {}",
+                batch.trades.len(), batch.cancels.len(), self.symbol, attempt, e
+            ));
+            tokio::time::sleep((Duration::from_millis(500) * attempt).min(MAX_RETRY_BACKOFF)).await;
+            attempt += 1;
+        }
+    }
+
+    // 按顺序补写落盘的事件，读取失败时稍后重试
+    async fn drain_spill(&self, spill: &Spill) {
+        match spill.take().await {
+            Ok(Some(events)) => {
+                async_info!(format!("Persisting {} spilled events of {}", events.len(), self.symbol));
+                let mut events = events.into_iter().peekable();
+                while events.peek().is_some() {
+                    self.write(events.by_ref().take(self.batch_size).collect()).await;
+                }
+                if let Err(e) = spill.finish_draining().await {
+                    async_error!(format!("Failed to remove drained spill file of {}: {}", self.symbol, e));
+                    tokio::time::sleep(MAX_RETRY_BACKOFF).await;
+                }
+            }
+            Ok(None) => async_info!(format!("Persistence of {} caught up, spill drained", self.symbol)),
+            Err(e) => {
+                async_error!(format!("[ALERT] Failed to read spilled persistence events of {}: {}", self.symbol, e));
+                tokio::time::sleep(MAX_RETRY_BACKOFF).await;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use crate::order_book::OrderBook;
+    use crate::order::Side;
+
+    #[tokio::test]
+    async fn test_collect_persist_events() {
+        let mut order_book = OrderBook::new();
+        let first = Order::new(1, 100.0, 1.0, OrderType::Limit, Side::Sell);
+        let second = Order::new(2, 101.0, 1.0, OrderType::Limit, Side::Sell);
+        order_book.add_order(first.clone());
+        order_book.add_order(second.clone());
+        order_book.take_book_events();
+
+        // 市价单吃掉一档后剩余部分被撤销
+        let market = Order::new(3, 0.0, 1.5, OrderType::Market, Side::Buy);
+        let mut results = order_book.add_order(market.clone());
+        for (seq_id, result) in results.iter_mut().enumerate() {
+            result.seq_id = seq_id as u64 + 1;
+        }
+        let events = PersistEvent::collect(1000, Some(&market), &results, &order_book.take_book_events());
+        let trades: Vec<_> = events.iter()
+            .filter_map(|event| match event {
+                PersistEvent::Trade { seq_id, trade } => Some((*seq_id, trade)),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].0, 1);
+        assert_eq!(trades[0].1.sell_order_id, first.id);
+        assert_eq!(events.last(), Some(&PersistEvent::Cancel { order_id: market.id.to_string(), timestamp: 1000 }));
+
+        // 部分撤单不改变订单状态，整单撤销才标记
+        let results = order_book.cancel_order(second.id, 0.2);
+        assert!(PersistEvent::collect(2000, None, &results, &order_book.take_book_events()).is_empty());
+        let results = order_book.cancel_order(second.id, 0.0);
+        let events = PersistEvent::collect(3000, None, &results, &order_book.take_book_events());
+        assert_eq!(events, vec![PersistEvent::Cancel { order_id: second.id.to_string(), timestamp: 3000 }]);
+
+        // 无法转换为 Decimal 的成交不进入批次
+        let mut invalid = trades[1].1.clone();
+        invalid.price = f64::NAN;
+        let (batch, skipped) = Batch::new("BTC/USDT", vec![
+            events[0].clone(),
+            PersistEvent::Trade { seq_id: trades[0].0, trade: trades[0].1.clone() },
+            PersistEvent::Trade { seq_id: trades[1].0, trade: invalid },
+        ]);
+        assert_eq!(batch.trades.len(), 1);
+        assert_eq!(batch.trades[0].sell_order_id, first.id.to_string());
+        assert_eq!(batch.trades[0].turnover(), Decimal::from(100));
+        assert_eq!(batch.cancels, vec![(second.id.to_string(), 3000)]);
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].0, trades[1].0);
+    }
+
+    #[tokio::test]
+    async fn test_spill_keeps_event_order() {
+        let dir = std::env::temp_dir().join(format!("spot_match_spill_{}", std::process::id()));
+        let spill = Spill::new(dir.to_str().unwrap(), "BTC/USDT");
+        assert!(!spill.is_active());
+
+        let cancel = |order_id: u64| PersistEvent::Cancel { order_id: order_id.to_string(), timestamp: 1000 };
+        spill.append(&[cancel(1), cancel(2)]).await.unwrap();
+        assert!(spill.is_active());
+        assert_eq!(spill.take().await.unwrap(), Some(vec![cancel(1), cancel(2)]));
+
+        // 处理中追加的事件排在之后，未处理完的文件重启后仍能取出
+        spill.append(&[cancel(3)]).await.unwrap();
+        let restarted = Spill::new(dir.to_str().unwrap(), "BTC/USDT");
+        assert!(restarted.is_active());
+        assert_eq!(restarted.take().await.unwrap(), Some(vec![cancel(1), cancel(2)]));
+        restarted.finish_draining().await.unwrap();
+        assert_eq!(restarted.take().await.unwrap(), Some(vec![cancel(3)]));
+        restarted.finish_draining().await.unwrap();
+
+        // 全部写完后结束溢出状态
+        assert_eq!(restarted.take().await.unwrap(), None);
+        assert!(!restarted.is_active());
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,82 @@
+use std::path::PathBuf;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use crate::date::current_timestamp;
+use crate::dedup::ClientOrderKey;
+use crate::order::Order;
+use crate::kline::KlineAggregator;
+use crate::order_book::OrderBook;
+use crate::ticker::RollingStats;
+
+// 订单簿快照，记录某一时刻的全部挂单以及对应的输入/输出位置
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderBookSnapshot {
+    pub symbol: String,
+    pub seq_id: u64,      // 快照时最后一条输出的序列号
+    #[serde(default)]
+    pub depth_seq_id: u64, // 快照时最后一条深度增量的序列号
+    #[serde(default)]
+    pub book_seq_id: u64, // 快照时最后一条逐笔变化的序列号
+    pub offset: i64,      // 快照时最后一条已撮合输入消息的 offset，-1 表示尚未消费
+    #[serde(default)]
+    pub halted: bool,     // 是否已被管理指令暂停撮合
+    pub timestamp: u64,   // 毫秒级时间戳
+    pub bids: Vec<Order>,
+    pub asks: Vec<Order>,
+    #[serde(default)]
+    pub stats: RollingStats, // 24 小时滚动成交统计
+    #[serde(default)]
+    pub klines: KlineAggregator, // 各周期未收盘的 K 线
+    #[serde(default)]
+    pub client_order_ids: Vec<ClientOrderKey>, // 去重窗口中的 (用户, 客户端订单号)，按受理顺序排列
+}
+
+impl OrderBookSnapshot {
+    /// 深度和逐笔变化的序列号默认为 0、未暂停、成交统计、K 线和去重窗口默认为空，由调用方按需填入
+    pub fn capture(symbol: &str, order_book: &OrderBook, seq_id: u64, offset: i64) -> Self {
+        let (bids, asks) = order_book.resting_orders();
+        OrderBookSnapshot {
+            symbol: symbol.to_string(),
+            seq_id,
+            depth_seq_id: 0,
+            book_seq_id: 0,
+            offset,
+            halted: false,
+            timestamp: current_timestamp(),
+            bids,
+            asks,
+            stats: RollingStats::new(),
+            klines: KlineAggregator::new(),
+            client_order_ids: Vec::new(),
+        }
+    }
+
+    pub fn into_order_book(self) -> OrderBook {
+        OrderBook::restore(self.bids, self.asks)
+    }
+
+    // 交易对中的 '/' 不能出现在文件名中，例如 BTC/USDT -> BTC_USDT.json
+    fn path(dir: &str, symbol: &str) -> PathBuf {
+        PathBuf::from(dir).join(format!("{}.json", symbol.replace('/', "_")))
+    }
+
+    /// 写入快照文件，先写临时文件再重命名，避免中途退出留下损坏的快照
+    pub async fn save(&self, dir: &str) -> Result<()> {
+        tokio::fs::create_dir_all(dir).await?;
+        let path = Self::path(dir, &self.symbol);
+        let tmp_path = path.with_extension("json.tmp");
+        tokio::fs::write(&tmp_path, serde_json::to_vec(self)?).await?;
+        tokio::fs::rename(&tmp_path, &path).await?;
+        Ok(())
+    }
+
+    /// 读取交易对的快照，不存在时返回 None
+    pub async fn load(dir: &str, symbol: &str) -> Result<Option<Self>> {
+        let path = Self::path(dir, symbol);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let content = tokio::fs::read(&path).await?;
+        Ok(Some(serde_json::from_slice(&content)?))
+    }
+}
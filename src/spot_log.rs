@@ -1,6 +1,12 @@
 use std::fmt;
+use anyhow::{anyhow, Result};
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
 use serde::{Deserialize, Serialize};
 use tklog::async_info;
+use crate::admin::AdminAction;
+use crate::config::MessageFormat;
+use crate::execution::ExecutionReport;
+use crate::fbs::spot_log_generated::spot_log::{LogType as FbsLogType, SpotLog as FbsSpotLog, SpotLogArgs};
 use crate::order::Order;
 use crate::trade::Trade;
 
@@ -9,10 +15,75 @@ pub struct SpotLog{
     pub log_type: LogType,
     pub seq_id: u64,
     pub order: Option<Order>,
-    pub trade: Option<Trade>
+    pub trade: Option<Trade>,
+    #[serde(default)]
+    pub admin: Option<AdminAction>,  // 管理操作，仅 LogType::Admin 使用
+    #[serde(default)]
+    pub report: Option<ExecutionReport>,  // 执行回报，仅 LogType::ExecutionReport 使用
 }
 
 impl SpotLog {
+    /// 按指定格式编码
+    pub fn encode(&self, format: MessageFormat) -> Result<Vec<u8>, serde_json::Error> {
+        match format {
+            MessageFormat::Json => serde_json::to_vec(self),
+            MessageFormat::Flatbuffers => Ok(self.to_flatbuffer()),
+        }
+    }
+
+    /// 按指定格式解码
+    pub fn decode(payload: &[u8], format: MessageFormat) -> Result<Self> {
+        match format {
+            MessageFormat::Json => Ok(serde_json::from_slice(payload)?),
+            MessageFormat::Flatbuffers => Self::parse_spot_log_flatbuffer(payload).map_err(|e| anyhow!(e)),
+        }
+    }
+
+    /// 从 FlatBuffers 数据解析出 SpotLog 实例
+    pub fn parse_spot_log_flatbuffer(data: &[u8]) -> Result<Self, &'static str> {
+        let fbs_spot_log = flatbuffers::root::<FbsSpotLog>(data).map_err(|_| "Failed to parse FlatBuffer data as SpotLog")?;
+        Self::from_flatbuffer(&fbs_spot_log)
+    }
+
+    /// 将 FlatBuffers 中的 SpotLog 表转换成 Rust 的 SpotLog 实例，也用于嵌套在信封中的消息体
+    pub fn from_flatbuffer(fbs_spot_log: &FbsSpotLog) -> Result<Self, &'static str> {
+        Ok(SpotLog {
+            log_type: fbs_spot_log.log_type().try_into()?,
+            seq_id: fbs_spot_log.seq_id(),
+            order: fbs_spot_log.order().map(|order| Order::from_flatbuffer(&order)).transpose()?,
+            trade: fbs_spot_log.trade().map(|trade| Trade::from_flatbuffer(&trade)),
+            admin: fbs_spot_log.admin().map(|admin| AdminAction::from_flatbuffer(&admin)).transpose()?,
+            report: fbs_spot_log.report().map(|report| ExecutionReport::from_flatbuffer(&report)).transpose()?,
+        })
+    }
+
+    /// 将 SpotLog 实例序列化为 FlatBuffers 格式
+    pub fn to_flatbuffer(&self) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::with_capacity(256);
+        let spot_log = self.create_flatbuffer(&mut builder);
+        builder.finish(spot_log, None);
+
+        builder.finished_data().to_vec()
+    }
+
+    /// 在 builder 中写入 SpotLog 表，返回其偏移，供外层表引用
+    pub fn create_flatbuffer<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<FbsSpotLog<'a>> {
+        let order = self.order.as_ref().map(|order| order.create_flatbuffer(builder));
+        let trade = self.trade.as_ref().map(|trade| trade.create_flatbuffer(builder));
+        let admin = self.admin.as_ref().map(|admin| admin.create_flatbuffer(builder));
+        let report = self.report.as_ref().map(|report| report.create_flatbuffer(builder));
+        FbsSpotLog::create(
+            builder,
+            &SpotLogArgs {
+                log_type: self.log_type.into(),
+                seq_id: self.seq_id,
+                order,
+                trade,
+                admin,
+                report,
+            },
+        )
+    }
 
     // 打印 SpotLog 的数据
     pub async fn print_data(&self) {
@@ -40,10 +111,41 @@ impl SpotLog {
 pub enum LogType{
     //新订单
     NewOrder,
-    //取消订单
+    //取消订单，order.quantity 为撤销数量，为 0 或不小于剩余数量时全部撤销
     CancelOrder,
     //订单成交
     Trade,
+    //管理操作：暂停、恢复、批量撤单、快照、日志级别，执行后原样发布到撮合结果主题留档
+    Admin,
+    //订单执行回报：受理、拒绝、挂单、成交、撤单、失效，只出现在撮合结果主题
+    ExecutionReport,
+}
+
+impl From<LogType> for FbsLogType {
+    fn from(log_type: LogType) -> Self {
+        match log_type {
+            LogType::NewOrder => FbsLogType::NewOrder,
+            LogType::CancelOrder => FbsLogType::CancelOrder,
+            LogType::Trade => FbsLogType::Trade,
+            LogType::Admin => FbsLogType::Admin,
+            LogType::ExecutionReport => FbsLogType::ExecutionReport,
+        }
+    }
+}
+
+impl TryFrom<FbsLogType> for LogType {
+    type Error = &'static str;
+
+    fn try_from(log_type: FbsLogType) -> Result<Self, &'static str> {
+        match log_type {
+            FbsLogType::NewOrder => Ok(LogType::NewOrder),
+            FbsLogType::CancelOrder => Ok(LogType::CancelOrder),
+            FbsLogType::Trade => Ok(LogType::Trade),
+            FbsLogType::Admin => Ok(LogType::Admin),
+            FbsLogType::ExecutionReport => Ok(LogType::ExecutionReport),
+            _ => Err("Invalid log type in FlatBuffer data"),
+        }
+    }
 }
 
 impl fmt::Display for LogType {
@@ -52,7 +154,62 @@ impl fmt::Display for LogType {
             LogType::NewOrder => "NewOrder",
             LogType::CancelOrder => "CancelOrder",
             LogType::Trade => "Trade",
+            LogType::Admin => "Admin",
+            LogType::ExecutionReport => "ExecutionReport",
         };
         write!(f, "{}", log_type_str)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::admin::AdminCommand;
+    use crate::execution::ExecutionStatus;
+    use crate::order::{OrderType, Side};
+
+    #[test]
+    fn test_spot_log_round_trip() {
+        let spot_logs = vec![
+            SpotLog {
+                log_type: LogType::NewOrder,
+                seq_id: 0,
+                order: Some(Order { id: 7, user_id: 3, price: 101.5, quantity: 2.0, timestamp: 1000, order_type: OrderType::Limit, side: Side::Sell, client_order_id: None, filled_quantity: 0.0 }),
+                trade: None,
+                admin: None,
+                report: None,
+            },
+            SpotLog {
+                log_type: LogType::Trade,
+                seq_id: 42,
+                order: None,
+                trade: Some(Trade { buy_order_id: 8, sell_order_id: 7, price: 101.5, quantity: 0.5, timestamp: 1001 }),
+                admin: None,
+                report: None,
+            },
+            AdminAction {
+                command: AdminCommand::CancelAll { user_id: Some(0) },
+                operator: "ops".to_string(),
+                reason: None,
+                timestamp: 1002,
+            }.into_spot_log(),
+            ExecutionReport::rejected(
+                &Order { client_order_id: Some("c-7".to_string()), ..Order::new(3, 101.5, 2.0, OrderType::Limit, Side::Sell) },
+                "trading halted".to_string(),
+            ).into_spot_log(),
+            ExecutionReport::new(
+                &Order { filled_quantity: 0.5, ..Order::new(3, 101.5, 2.0, OrderType::Limit, Side::Buy) },
+                ExecutionStatus::PartiallyFilled,
+                1.5,
+            ).with_last(0.5, 101.0).into_spot_log(),
+        ];
+
+        for spot_log in spot_logs {
+            for format in [MessageFormat::Json, MessageFormat::Flatbuffers] {
+                let decoded = SpotLog::decode(&spot_log.encode(format).unwrap(), format).unwrap();
+                assert_eq!(serde_json::to_value(&decoded).unwrap(), serde_json::to_value(&spot_log).unwrap());
+            }
+        }
+        assert!(SpotLog::decode(b"{}", MessageFormat::Flatbuffers).is_err());
+    }
+}
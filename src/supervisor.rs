@@ -0,0 +1,415 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use tklog::{async_error, async_info};
+use tokio::sync::watch;
+use tokio::task::{JoinError, JoinHandle};
+
+use crate::config::{Config, SymbolConfig};
+use crate::db_pool::get_database;
+use crate::engine::{Engine, EngineRole};
+use crate::health::HealthRegistry;
+use crate::lease::LeaseKeeper;
+use crate::market_data::MarketDataStore;
+use crate::metrics;
+use crate::model::config_symbol_matching::ConfigSymbolMatching;
+use crate::network::get_ip_addresses;
+use crate::node::node_id;
+
+// 引擎运行状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum EngineStatus {
+    //等待取得交易对租约
+    WaitingLease,
+    //热备运行，撮合但不发布
+    Standby,
+    //正常运行
+    Running,
+    //异常退出，等待重启
+    Restarting,
+    //超过最大重启次数，已放弃
+    Failed,
+}
+
+impl fmt::Display for EngineStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let status_str = match self {
+            EngineStatus::WaitingLease => "WaitingLease",
+            EngineStatus::Standby => "Standby",
+            EngineStatus::Running => "Running",
+            EngineStatus::Restarting => "Restarting",
+            EngineStatus::Failed => "Failed",
+        };
+        write!(f, "{}", status_str)
+    }
+}
+
+/// 各交易对引擎状态，可在监管器运行前通过 `Supervisor::statuses` 取得并共享给其他模块
+pub type EngineStatuses = Arc<RwLock<HashMap<String, EngineStatus>>>;
+
+// 重启策略：指数退避，连续失败超过上限后标记为失败
+#[derive(Debug, Clone, Copy)]
+struct RestartPolicy {
+    max_attempts: u32,
+    backoff: Duration,
+    backoff_max: Duration,
+}
+
+impl RestartPolicy {
+    fn from_config() -> Self {
+        let config = Config::global();
+        RestartPolicy {
+            max_attempts: config.engine_config.restart_max_attempts,
+            backoff: Duration::from_millis(config.engine_config.restart_backoff_ms),
+            backoff_max: Duration::from_millis(config.engine_config.restart_backoff_max_ms),
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.backoff
+            .saturating_mul(2u32.saturating_pow(attempt.saturating_sub(1)))
+            .min(self.backoff_max)
+    }
+}
+
+// 运行中的引擎句柄
+struct EngineHandle {
+    shutdown_sender: watch::Sender<bool>,
+    handle: JoinHandle<()>,
+}
+
+/// 引擎监管器：定期重新读取交易对配置（config_symbol_matching 或配置文件），按 is_open 启动或停止单个交易对的引擎，
+/// 并在引擎崩溃时从最近一次快照按退避策略重启
+#[derive(Default)]
+pub struct Supervisor {
+    engines: HashMap<String, EngineHandle>,
+    statuses: EngineStatuses,
+    market_data: MarketDataStore,
+    health: Arc<HealthRegistry>,
+}
+
+impl Supervisor {
+    pub fn new() -> Self {
+        Supervisor {
+            engines: HashMap::new(),
+            statuses: Arc::new(RwLock::new(HashMap::new())),
+            market_data: MarketDataStore::with_stream_capacity(Config::global().http_config.ws_buffer_size),
+            health: Arc::new(HealthRegistry::new()),
+        }
+    }
+
+    /// 共享的引擎状态表
+    pub fn statuses(&self) -> EngineStatuses {
+        self.statuses.clone()
+    }
+
+    /// 共享的行情存储
+    pub fn market_data(&self) -> MarketDataStore {
+        self.market_data.clone()
+    }
+
+    /// 共享的引擎健康状态，供存活和就绪探针使用
+    pub fn health(&self) -> Arc<HealthRegistry> {
+        self.health.clone()
+    }
+
+    /// 运行监管循环，直到 `shutdown` 收到关闭信号后排空所有引擎
+    pub async fn run(mut self, mut shutdown: watch::Receiver<bool>) {
+        let refresh_interval = {
+            let config = Config::global();
+            Duration::from_millis(config.engine_config.symbol_refresh_interval_ms)
+        };
+        let mut interval = tokio::time::interval(refresh_interval);
+
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {
+                    match load_symbol_configs().await {
+                        Ok(configs) => {
+                            self.reconcile(configs).await;
+                            self.health.set_symbols_loaded();
+                        }
+                        Err(e) => async_error!(format!("Failed to refresh symbol configs: {}", e)),
+                    }
+                }
+                changed = shutdown.changed() => {
+                    if changed.is_err() || *shutdown.borrow() {
+                        break;
+                    }
+                }
+            }
+        }
+
+        self.shutdown_all().await;
+    }
+
+    /// 将运行中的引擎与最新配置对齐：启动新开放的交易对，停止已关闭或已移除的交易对。
+    /// 已标记为失败的引擎保持失败状态，需关闭后重新开放才会再次启动；
+    /// 运行中交易对的规则变更在引擎下次启动时生效
+    pub async fn reconcile(&mut self, configs: Vec<SymbolConfig>) {
+        let mut wanted = HashMap::new();
+        for symbol_config in configs.into_iter().filter(|c| c.is_open == 1) {
+            wanted.insert(symbol_config.symbol(), symbol_config);
+        }
+
+        let to_stop: Vec<String> = self.engines.keys()
+            .filter(|symbol| !wanted.contains_key(*symbol))
+            .cloned()
+            .collect();
+        self.stop_engines(to_stop).await;
+
+        for (symbol, symbol_config) in wanted {
+            if !self.engines.contains_key(&symbol) {
+                self.start_engine(symbol, symbol_config).await;
+            }
+        }
+    }
+
+    /// 当前管理中的交易对
+    pub fn symbols(&self) -> Vec<String> {
+        self.engines.keys().cloned().collect()
+    }
+
+    async fn start_engine(&mut self, symbol: String, symbol_config: SymbolConfig) {
+        async_info!("Starting engine: ", symbol.clone());
+
+        // 每个引擎独立的关闭信号
+        let (shutdown_sender, shutdown_receiver) = watch::channel(false);
+        let handle = tokio::spawn(supervise_engine(
+            symbol.clone(),
+            symbol_config,
+            shutdown_receiver,
+            self.statuses.clone(),
+            self.market_data.clone(),
+            self.health.clone(),
+            RestartPolicy::from_config(),
+        ));
+
+        self.engines.insert(symbol, EngineHandle { shutdown_sender, handle });
+    }
+
+    // 并行通知引擎停止，并等待它们排空完毕
+    async fn stop_engines(&mut self, symbols: Vec<String>) {
+        let mut stopping = Vec::new();
+        for symbol in symbols {
+            if let Some(engine) = self.engines.remove(&symbol) {
+                async_info!("Stopping engine: ", symbol.clone());
+                let _ = engine.shutdown_sender.send(true);
+                stopping.push((symbol, engine.handle));
+            }
+        }
+
+        for (symbol, handle) in stopping {
+            if let Err(e) = handle.await {
+                async_error!(format!("Engine {} task error: {}", symbol, e));
+            }
+            self.statuses.write().expect("engine statuses poisoned").remove(&symbol);
+            self.market_data.remove(&symbol);
+            metrics::remove(&symbol);
+            self.health.remove(&symbol);
+        }
+    }
+
+    /// 停止所有引擎，每个引擎内部按 engine_config.shutdown_timeout_ms 限时
+    pub async fn shutdown_all(&mut self) {
+        let symbols = self.symbols();
+        self.stop_engines(symbols).await;
+    }
+}
+
+fn set_status(statuses: &EngineStatuses, symbol: &str, status: EngineStatus) {
+    statuses.write().expect("engine statuses poisoned").insert(symbol.to_string(), status);
+}
+
+// 单个交易对的监管任务：开启租约时先取得租约再启动引擎；引擎在独立任务中运行，
+// panic 或返回错误时按退避策略从快照重启，租约丢失时停止引擎并重新等待租约
+async fn supervise_engine(
+    symbol: String,
+    symbol_config: SymbolConfig,
+    mut shutdown: watch::Receiver<bool>,
+    statuses: EngineStatuses,
+    market_data: MarketDataStore,
+    health: Arc<HealthRegistry>,
+    policy: RestartPolicy,
+) {
+    let lease = match LeaseKeeper::from_config(&symbol) {
+        Ok(lease) => lease,
+        Err(e) => {
+            async_error!(format!("[ALERT] Engine {} not started: {}", symbol, e));
+            set_status(&statuses, &symbol, EngineStatus::Failed);
+            return;
+        }
+    };
+    // 热备模式下无需等待租约，引擎以热备角色先行撮合，取得租约后再接管发布
+    let standby = lease.is_some() && Config::global().node_config.standby_enabled;
+    let mut attempts = 0;
+
+    loop {
+        if standby {
+            set_status(&statuses, &symbol, EngineStatus::Standby);
+        } else {
+            if let Some(lease) = &lease {
+                set_status(&statuses, &symbol, EngineStatus::WaitingLease);
+                if !lease.acquire(&mut shutdown).await {
+                    return;
+                }
+            }
+            set_status(&statuses, &symbol, EngineStatus::Running);
+        }
+        let started = Instant::now();
+
+        let mut engine = Engine::new(
+            symbol.clone(),
+            symbol_config.base.clone(),
+            symbol_config.quote.clone(),
+            symbol_config.rules.clone(),
+        );
+        engine.set_market_data(market_data.clone());
+        engine.set_spot_log_format(symbol_config.spot_log_format.unwrap_or(Config::global().engine_config.spot_log_format));
+        engine.set_health(health.register(&symbol));
+        let (result, lease_lost) = run_engine(engine, &mut shutdown, lease.as_ref(), standby, &statuses).await;
+
+        if *shutdown.borrow() {
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => async_error!(format!("Engine {} error during shutdown: {}", symbol, e)),
+                Err(e) => async_error!(format!("Engine {} task error during shutdown: {}", symbol, e)),
+            }
+            if let Some(lease) = &lease {
+                lease.release().await;
+            }
+            return;
+        }
+
+        if lease_lost {
+            async_error!(format!("[ALERT] Engine {} stopped after losing its lease", symbol));
+            continue;
+        }
+
+        let reason = match result {
+            Ok(Ok(())) => "exited unexpectedly".to_string(),
+            Ok(Err(e)) => format!("returned error: {}", e),
+            Err(e) if e.is_panic() => format!("panicked: {}", e),
+            Err(e) => format!("task failed: {}", e),
+        };
+        async_error!(format!("[ALERT] Engine {} {}", symbol, reason));
+
+        // 释放租约，让健康的节点有机会接管
+        if let Some(lease) = &lease {
+            lease.release().await;
+        }
+
+        // 稳定运行超过退避上限后视为恢复，重新计算重启次数
+        if started.elapsed() > policy.backoff_max {
+            attempts = 0;
+        }
+        attempts += 1;
+
+        if attempts > policy.max_attempts {
+            async_error!(format!("[ALERT] Engine {} failed {} times in a row, giving up", symbol, attempts - 1));
+            set_status(&statuses, &symbol, EngineStatus::Failed);
+            return;
+        }
+
+        let backoff = policy.backoff(attempts);
+        set_status(&statuses, &symbol, EngineStatus::Restarting);
+        async_info!(format!("Restarting engine {} from last snapshot in {:?} (attempt {})", symbol, backoff, attempts));
+
+        tokio::select! {
+            _ = tokio::time::sleep(backoff) => {}
+            _ = shutdown.changed() => return,
+        }
+    }
+}
+
+// 在独立任务中运行引擎，外部关闭或租约丢失时通知引擎排空，返回引擎结果以及是否因租约丢失而停止。
+// 热备模式下引擎以热备角色启动，取得租约后切换为主节点
+async fn run_engine(
+    mut engine: Engine,
+    shutdown: &mut watch::Receiver<bool>,
+    lease: Option<&LeaseKeeper>,
+    standby: bool,
+    statuses: &EngineStatuses,
+) -> (Result<Result<()>, JoinError>, bool) {
+    let symbol = engine.symbol().to_string();
+    let (stop_sender, stop_receiver) = watch::channel(false);
+    let (role_sender, role_receiver) = watch::channel(if standby { EngineRole::Standby } else { EngineRole::Primary });
+    engine.set_role(role_receiver);
+    let mut task = tokio::spawn(async move { engine.run(stop_receiver).await });
+
+    let mut acquire_shutdown = shutdown.clone();
+    let hold_lease = async {
+        let Some(lease) = lease else {
+            return std::future::pending().await;
+        };
+        if standby {
+            if !lease.acquire(&mut acquire_shutdown).await {
+                return std::future::pending().await;
+            }
+            let _ = role_sender.send(EngineRole::Primary);
+            set_status(statuses, &symbol, EngineStatus::Running);
+        }
+        lease.hold().await
+    };
+    tokio::pin!(hold_lease);
+
+    let mut stopping = false;
+    let mut lease_lost = false;
+    loop {
+        tokio::select! {
+            result = &mut task => return (result, lease_lost),
+            changed = shutdown.changed(), if !stopping => {
+                if changed.is_err() || *shutdown.borrow() {
+                    stopping = true;
+                    let _ = stop_sender.send(true);
+                }
+            }
+            _ = &mut hold_lease, if !stopping => {
+                stopping = true;
+                lease_lost = true;
+                // 先停止发布再排空，排空期间接管的节点可能已开始发布
+                let _ = role_sender.send(EngineRole::Standby);
+                let _ = stop_sender.send(true);
+            }
+        }
+    }
+}
+
+/// 按 symbol_config.source 读取分配给本机的交易对配置，source 为 both 时同一交易对以配置文件为准
+pub async fn load_symbol_configs() -> Result<Vec<SymbolConfig>> {
+    let config = Config::global();
+    let source = config.symbol_config.source;
+
+    let mut symbol_configs: HashMap<String, SymbolConfig> = HashMap::new();
+    if source.uses_db() {
+        for matching in load_db_symbol_configs().await? {
+            let symbol_config = SymbolConfig::from(matching);
+            symbol_configs.insert(symbol_config.symbol(), symbol_config);
+        }
+    }
+    if source.uses_file() {
+        for symbol_config in &config.symbol_config.symbols {
+            symbol_configs.insert(symbol_config.symbol(), symbol_config.clone());
+        }
+    }
+
+    Ok(symbol_configs.into_values().collect())
+}
+
+// 读取分配给本节点的交易对配置：配置了节点标识时按标识匹配 server 字段，否则按本机 IP 匹配
+async fn load_db_symbol_configs() -> Result<Vec<ConfigSymbolMatching>> {
+    let db = get_database()?;
+    if let Some(node_id) = node_id() {
+        return Ok(ConfigSymbolMatching::get_configs_by_servers(&db, vec![node_id]).await?);
+    }
+
+    let interfaces = get_ip_addresses()?;
+    if interfaces.is_empty() {
+        return Err(anyhow!("获取机器IP为空"));
+    }
+
+    Ok(ConfigSymbolMatching::get_configs_by_servers(&db, interfaces).await?)
+}
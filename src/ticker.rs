@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+use serde::{Deserialize, Serialize};
+
+// 统计窗口 24 小时，按分钟分桶，窗口随时间逐桶滑动
+const WINDOW_MS: u64 = 24 * 60 * 60 * 1000;
+const BUCKET_MS: u64 = 60 * 1000;
+
+// 一分钟内的成交汇总
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Bucket {
+    start: u64,
+    open: f64,
+    high: f64,
+    low: f64,
+    volume: f64,
+    quote_volume: f64,
+    trade_count: u64,
+}
+
+/// 24 小时滚动成交统计，随成交增量更新，可随快照持久化
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RollingStats {
+    buckets: VecDeque<Bucket>,
+    high: Option<f64>,
+    low: Option<f64>,
+    volume: f64,
+    quote_volume: f64,
+    trade_count: u64,
+    last_price: Option<f64>,
+    last_quantity: f64,
+    last_timestamp: u64,
+}
+
+// 行情快照
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ticker {
+    pub symbol: String,
+    pub last_price: Option<f64>,
+    pub last_quantity: f64,
+    pub best_bid: Option<f64>,
+    pub best_ask: Option<f64>,
+    pub open: Option<f64>,           // 24 小时窗口内第一笔成交价
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub volume: f64,                 // 24 小时成交量（基础币）
+    pub quote_volume: f64,           // 24 小时成交额（计价币）
+    pub price_change: Option<f64>,   // 最新价 - 开盘价
+    pub trade_count: u64,
+    pub timestamp: u64,              // 毫秒级时间戳
+}
+
+impl RollingStats {
+    pub fn new() -> Self {
+        RollingStats::default()
+    }
+
+    /// 记录一笔成交，时间取自成交时间戳
+    pub fn add_trade(&mut self, price: f64, quantity: f64, timestamp: u64) {
+        self.evict(timestamp);
+
+        let start = timestamp - timestamp % BUCKET_MS;
+        match self.buckets.back_mut() {
+            Some(bucket) if bucket.start >= start => {
+                bucket.high = bucket.high.max(price);
+                bucket.low = bucket.low.min(price);
+                bucket.volume += quantity;
+                bucket.quote_volume += price * quantity;
+                bucket.trade_count += 1;
+            }
+            _ => self.buckets.push_back(Bucket {
+                start,
+                open: price,
+                high: price,
+                low: price,
+                volume: quantity,
+                quote_volume: price * quantity,
+                trade_count: 1,
+            }),
+        }
+
+        self.high = Some(self.high.map_or(price, |high| high.max(price)));
+        self.low = Some(self.low.map_or(price, |low| low.min(price)));
+        self.volume += quantity;
+        self.quote_volume += price * quantity;
+        self.trade_count += 1;
+        self.last_price = Some(price);
+        self.last_quantity = quantity;
+        self.last_timestamp = self.last_timestamp.max(timestamp);
+    }
+
+    // 移出已滑出窗口的分钟桶，并重新计算最高、最低价
+    fn evict(&mut self, now: u64) {
+        let window_start = now.saturating_sub(WINDOW_MS);
+        let mut evicted = false;
+        while let Some(bucket) = self.buckets.front() {
+            if bucket.start + BUCKET_MS > window_start {
+                break;
+            }
+            self.volume -= bucket.volume;
+            self.quote_volume -= bucket.quote_volume;
+            self.trade_count -= bucket.trade_count;
+            self.buckets.pop_front();
+            evicted = true;
+        }
+
+        if evicted {
+            if self.buckets.is_empty() {
+                // 清零，避免浮点误差累积
+                self.volume = 0.0;
+                self.quote_volume = 0.0;
+            }
+            self.high = self.buckets.iter().map(|b| b.high).reduce(f64::max);
+            self.low = self.buckets.iter().map(|b| b.low).reduce(f64::min);
+        }
+    }
+
+    /// 截至 `now` 的行情
+    pub fn ticker(&mut self, symbol: &str, best_bid: Option<f64>, best_ask: Option<f64>, now: u64) -> Ticker {
+        self.evict(now.max(self.last_timestamp));
+
+        let open = self.buckets.front().map(|b| b.open);
+        Ticker {
+            symbol: symbol.to_string(),
+            last_price: self.last_price,
+            last_quantity: self.last_quantity,
+            best_bid,
+            best_ask,
+            open,
+            high: self.high,
+            low: self.low,
+            volume: self.volume,
+            quote_volume: self.quote_volume,
+            price_change: self.last_price.zip(open).map(|(last, open)| last - open),
+            trade_count: self.trade_count,
+            timestamp: now,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rolling_stats_window() {
+        let mut stats = RollingStats::new();
+        let start = 1_700_000_000_000;
+        stats.add_trade(100.0, 1.0, start);
+        stats.add_trade(120.0, 2.0, start + 30_000);
+        stats.add_trade(90.0, 1.0, start + 2 * 60 * 60 * 1000);
+
+        let ticker = stats.ticker("BTC/USDT", Some(89.0), Some(91.0), start + 3 * 60 * 60 * 1000);
+        assert_eq!(ticker.open, Some(100.0));
+        assert_eq!(ticker.high, Some(120.0));
+        assert_eq!(ticker.low, Some(90.0));
+        assert_eq!(ticker.volume, 4.0);
+        assert_eq!(ticker.quote_volume, 430.0);
+        assert_eq!(ticker.price_change, Some(-10.0));
+        assert_eq!(ticker.trade_count, 3);
+
+        // 前两笔成交滑出窗口
+        let ticker = stats.ticker("BTC/USDT", None, None, start + WINDOW_MS + 60 * 60 * 1000);
+        assert_eq!(ticker.open, Some(90.0));
+        assert_eq!(ticker.high, Some(90.0));
+        assert_eq!(ticker.volume, 1.0);
+        assert_eq!(ticker.trade_count, 1);
+
+        // 窗口内无成交时保留最新价
+        let ticker = stats.ticker("BTC/USDT", None, None, start + 2 * WINDOW_MS);
+        assert_eq!(ticker.last_price, Some(90.0));
+        assert_eq!(ticker.open, None);
+        assert_eq!(ticker.volume, 0.0);
+    }
+}
@@ -7,7 +7,17 @@ pub enum Topic {
     //新订单
     SpotNewOrder,
     //撮合结果
-    SpotMatchResult
+    SpotMatchResult,
+    //增量深度及全量深度快照
+    SpotDepthUpdate,
+    //逐笔订单簿变化
+    SpotBookEvent,
+    //行情及 24 小时统计
+    SpotTicker,
+    //K 线更新及收盘
+    SpotKline,
+    //无法撮合的输入消息
+    SpotDeadLetter,
 }
 
 impl fmt::Display for Topic {
@@ -15,6 +25,11 @@ impl fmt::Display for Topic {
         let topic_str = match self {
             Topic::SpotNewOrder => "SpotNewOrder",
             Topic::SpotMatchResult => "SpotMatchResult",
+            Topic::SpotDepthUpdate => "SpotDepthUpdate",
+            Topic::SpotBookEvent => "SpotBookEvent",
+            Topic::SpotTicker => "SpotTicker",
+            Topic::SpotKline => "SpotKline",
+            Topic::SpotDeadLetter => "SpotDeadLetter",
         };
         write!(f, "{}", topic_str)
     }
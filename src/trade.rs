@@ -1,8 +1,10 @@
 use std::fmt;
+use flatbuffers::{FlatBufferBuilder, WIPOffset};
 use serde::{Deserialize, Serialize};
+use crate::fbs::trade_generated::trade::{Trade as FbsTrade, TradeArgs};
 
 // 交易结构体
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Trade {
     pub buy_order_id: u64,
     pub sell_order_id: u64,
@@ -24,3 +26,45 @@ impl fmt::Display for Trade {
         )
     }
 }
+
+impl Trade {
+    /// 从 FlatBuffers 数据解析出 Trade 实例
+    pub fn parse_trade_flatbuffer(data: &[u8]) -> Result<Self, &'static str> {
+        let fbs_trade = flatbuffers::root::<FbsTrade>(data).map_err(|_| "Failed to parse FlatBuffer data as Trade")?;
+        Ok(Self::from_flatbuffer(&fbs_trade))
+    }
+
+    /// 将 FlatBuffers 中的 Trade 表转换成 Rust 的 Trade 实例，也用于嵌套在 SpotLog 中的成交
+    pub fn from_flatbuffer(fbs_trade: &FbsTrade) -> Self {
+        Trade {
+            buy_order_id: fbs_trade.buy_order_id(),
+            sell_order_id: fbs_trade.sell_order_id(),
+            price: fbs_trade.price(),
+            quantity: fbs_trade.quantity(),
+            timestamp: fbs_trade.timestamp(),
+        }
+    }
+
+    /// 将 Trade 实例序列化为 FlatBuffers 格式
+    pub fn to_flatbuffer(&self) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::with_capacity(256);
+        let trade = self.create_flatbuffer(&mut builder);
+        builder.finish(trade, None);
+
+        builder.finished_data().to_vec()
+    }
+
+    /// 在 builder 中写入 Trade 表，返回其偏移，供外层表引用
+    pub fn create_flatbuffer<'a>(&self, builder: &mut FlatBufferBuilder<'a>) -> WIPOffset<FbsTrade<'a>> {
+        FbsTrade::create(
+            builder,
+            &TradeArgs {
+                buy_order_id: self.buy_order_id,
+                sell_order_id: self.sell_order_id,
+                price: self.price,
+                quantity: self.quantity,
+                timestamp: self.timestamp,
+            },
+        )
+    }
+}
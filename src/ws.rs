@@ -0,0 +1,317 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use futures::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tklog::async_info;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use warp::reply::{self, Reply};
+use warp::ws::{Message, WebSocket, Ws};
+use warp::Filter;
+
+use crate::market_data::{MarketDataStore, MarketEvent, SymbolMarket};
+use crate::order_book::Depth;
+use crate::ticker::Ticker;
+use crate::trade::Trade;
+
+// 单个连接待发送消息的队列长度。客户端读取过慢时队列被占满，订阅任务随之落后，
+// 落后超过推送队列长度后丢弃积压的增量，重新发送快照
+const OUTBOUND_CAPACITY: usize = 256;
+
+// 频道类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChannelKind {
+    Depth,
+    Trades,
+    Ticker,
+}
+
+/// 订阅频道，格式为 `类型@交易对`，例如 `depth@BTC/USDT`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Channel {
+    pub kind: ChannelKind,
+    pub symbol: String,
+}
+
+impl FromStr for Channel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, symbol) = s.split_once('@')
+            .ok_or_else(|| format!("invalid channel {}, expected <type>@<symbol>", s))?;
+        let kind = match kind {
+            "depth" => ChannelKind::Depth,
+            "trades" => ChannelKind::Trades,
+            "ticker" => ChannelKind::Ticker,
+            _ => return Err(format!("unknown channel type {}", kind)),
+        };
+        if symbol.is_empty() {
+            return Err(format!("missing symbol in channel {}", s));
+        }
+        Ok(Channel { kind, symbol: symbol.to_string() })
+    }
+}
+
+impl fmt::Display for Channel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind_str = match self.kind {
+            ChannelKind::Depth => "depth",
+            ChannelKind::Trades => "trades",
+            ChannelKind::Ticker => "ticker",
+        };
+        write!(f, "{}@{}", kind_str, self.symbol)
+    }
+}
+
+// 客户端请求，例如 {"op": "subscribe", "channels": ["depth@BTC/USDT"]}
+#[derive(Debug, Deserialize)]
+#[serde(tag = "op", rename_all = "lowercase")]
+enum ClientRequest {
+    Subscribe { channels: Vec<String> },
+    Unsubscribe { channels: Vec<String> },
+}
+
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum PushKind {
+    //全量快照：订阅时以及需要重新同步时发送，客户端用它替换本地状态
+    Snapshot,
+    //增量更新：seq_id 紧接上一条消息递增
+    Update,
+}
+
+// 推送给客户端的消息。同一频道内 seq_id 连续，快照的 seq_id 等于其已包含的最后一条增量
+#[derive(Debug, Serialize)]
+struct Push<'a, T: Serialize> {
+    channel: &'a str,
+    kind: PushKind,
+    seq_id: u64,
+    data: T,
+}
+
+#[derive(Debug, Serialize)]
+struct ErrorPush<'a> {
+    channel: Option<&'a str>,
+    error: String,
+}
+
+// 在视图读锁内取出的快照，序列化放到锁外进行
+enum Snapshot {
+    Depth(u64, Depth),
+    Trades(u64, Vec<Trade>),
+    Ticker(u64, Option<Ticker>),
+}
+
+impl Snapshot {
+    fn capture(kind: ChannelKind, market: &SymbolMarket) -> Self {
+        match kind {
+            ChannelKind::Depth => Snapshot::Depth(market.depth_seq_id(), market.depth(usize::MAX)),
+            ChannelKind::Trades => Snapshot::Trades(market.trade_seq_id(), market.trades(usize::MAX)),
+            ChannelKind::Ticker => Snapshot::Ticker(market.ticker_seq_id(), market.ticker()),
+        }
+    }
+
+    fn into_message(self, channel: &str) -> Message {
+        match self {
+            Snapshot::Depth(seq_id, data) => push(channel, PushKind::Snapshot, seq_id, data),
+            Snapshot::Trades(seq_id, data) => push(channel, PushKind::Snapshot, seq_id, data),
+            Snapshot::Ticker(seq_id, data) => push(channel, PushKind::Snapshot, seq_id, data),
+        }
+    }
+}
+
+fn push<T: Serialize>(channel: &str, kind: PushKind, seq_id: u64, data: T) -> Message {
+    let push = Push { channel, kind, seq_id, data };
+    Message::text(serde_json::to_string(&push).expect("market data is serializable"))
+}
+
+fn error_message(channel: Option<&str>, error: String) -> Message {
+    Message::text(serde_json::to_string(&ErrorPush { channel, error }).expect("error is serializable"))
+}
+
+// 视图变化中属于该频道的增量消息
+fn update_message(channel: &Channel, name: &str, event: MarketEvent) -> Option<Message> {
+    match (channel.kind, event) {
+        (ChannelKind::Depth, MarketEvent::Depth { seq_id, changes }) => Some(push(name, PushKind::Update, seq_id, changes)),
+        (ChannelKind::Trades, MarketEvent::Trade { seq_id, trade }) => Some(push(name, PushKind::Update, seq_id, trade)),
+        (ChannelKind::Ticker, MarketEvent::Ticker { seq_id, ticker }) => Some(push(name, PushKind::Update, seq_id, ticker)),
+        _ => None,
+    }
+}
+
+// 订阅频道：先发送快照，再转发增量。落后过多或视图被重置时重新发送快照，交易对停止撮合后结束
+fn subscribe(channel: Channel, market_data: MarketDataStore, outbound: mpsc::Sender<Message>) -> Option<JoinHandle<()>> {
+    let (snapshot, events) = market_data.read(&channel.symbol, |market| {
+        (Snapshot::capture(channel.kind, market), market.subscribe())
+    })?;
+
+    Some(tokio::spawn(async move {
+        let name = channel.to_string();
+        let mut snapshot = snapshot;
+        let mut events = events;
+        loop {
+            if outbound.send(snapshot.into_message(&name)).await.is_err() {
+                return;
+            }
+
+            loop {
+                match events.recv().await {
+                    Ok(MarketEvent::Reset) => break,
+                    Ok(event) => {
+                        if let Some(message) = update_message(&channel, &name, event) {
+                            if outbound.send(message).await.is_err() {
+                                return;
+                            }
+                        }
+                    }
+                    Err(RecvError::Lagged(skipped)) => {
+                        async_info!(format!("WebSocket subscriber of {} lagged by {} events, resyncing", name, skipped));
+                        break;
+                    }
+                    Err(RecvError::Closed) => {
+                        let _ = outbound.send(error_message(Some(&name), "symbol is no longer matched".to_string())).await;
+                        return;
+                    }
+                }
+            }
+
+            match market_data.read(&channel.symbol, |market| {
+                (Snapshot::capture(channel.kind, market), market.subscribe())
+            }) {
+                Some((next_snapshot, next_events)) => {
+                    snapshot = next_snapshot;
+                    events = next_events;
+                }
+                None => {
+                    let _ = outbound.send(error_message(Some(&name), "symbol is no longer matched".to_string())).await;
+                    return;
+                }
+            }
+        }
+    }))
+}
+
+// 单个 WebSocket 连接：读取订阅请求，所有频道的消息经同一队列写回客户端
+async fn session(socket: WebSocket, market_data: MarketDataStore) {
+    let (mut sink, mut stream) = socket.split();
+    let (outbound, mut outbound_receiver) = mpsc::channel::<Message>(OUTBOUND_CAPACITY);
+    let writer = tokio::spawn(async move {
+        while let Some(message) = outbound_receiver.recv().await {
+            if sink.send(message).await.is_err() {
+                break;
+            }
+        }
+        let _ = sink.close().await;
+    });
+
+    let mut subscriptions: HashMap<Channel, JoinHandle<()>> = HashMap::new();
+    while let Some(Ok(message)) = stream.next().await {
+        if message.is_close() {
+            break;
+        }
+        let Ok(text) = message.to_str() else {
+            continue;
+        };
+
+        match serde_json::from_str::<ClientRequest>(text) {
+            Ok(ClientRequest::Subscribe { channels }) => {
+                for name in channels {
+                    let channel = match name.parse::<Channel>() {
+                        Ok(channel) => channel,
+                        Err(e) => {
+                            let _ = outbound.send(error_message(Some(&name), e)).await;
+                            continue;
+                        }
+                    };
+                    if subscriptions.get(&channel).is_some_and(|handle| !handle.is_finished()) {
+                        continue;
+                    }
+                    match subscribe(channel.clone(), market_data.clone(), outbound.clone()) {
+                        Some(handle) => {
+                            subscriptions.insert(channel, handle);
+                        }
+                        None => {
+                            let error = format!("unknown symbol {}", channel.symbol);
+                            let _ = outbound.send(error_message(Some(&name), error)).await;
+                        }
+                    }
+                }
+            }
+            Ok(ClientRequest::Unsubscribe { channels }) => {
+                for name in channels {
+                    if let Some(handle) = name.parse::<Channel>().ok().and_then(|channel| subscriptions.remove(&channel)) {
+                        handle.abort();
+                    }
+                }
+            }
+            Err(e) => {
+                let _ = outbound.send(error_message(None, format!("invalid request: {}", e))).await;
+            }
+        }
+    }
+
+    for handle in subscriptions.into_values() {
+        handle.abort();
+    }
+    drop(outbound);
+    let _ = writer.await;
+}
+
+/// WebSocket 推送路由 `/ws`，客户端连接后发送订阅请求
+pub fn route(market_data: MarketDataStore) -> impl Filter<Extract = (reply::Response,), Error = warp::Rejection> + Clone {
+    warp::path!("ws")
+        .and(warp::ws())
+        .map(move |ws: Ws| {
+            let market_data = market_data.clone();
+            ws.on_upgrade(move |socket| session(socket, market_data)).into_response()
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::order::{Order, OrderType, Side};
+    use crate::order_book::OrderBook;
+
+    async fn recv_json(client: &mut warp::test::WsClient) -> serde_json::Value {
+        let message = client.recv().await.unwrap();
+        serde_json::from_str(message.to_str().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_depth_channel_snapshot_then_updates() {
+        let mut order_book = OrderBook::new();
+        order_book.add_order(Order::new(1, 100.0, 1.0, OrderType::Limit, Side::Buy));
+        order_book.take_depth_changes();
+
+        let market_data = MarketDataStore::new();
+        let symbol_market = market_data.symbol("BTC/USDT");
+        symbol_market.write().unwrap().reset(&order_book, 10, 5);
+
+        let mut client = warp::test::ws().path("/ws").handshake(route(market_data.clone())).await.unwrap();
+        client.send_text(r#"{"op":"subscribe","channels":["depth@ETH/USDT","depth@BTC/USDT"]}"#).await;
+
+        let error = recv_json(&mut client).await;
+        assert_eq!(error["channel"], "depth@ETH/USDT");
+
+        let snapshot = recv_json(&mut client).await;
+        assert_eq!(snapshot["kind"], "snapshot");
+        assert_eq!(snapshot["seq_id"], 5);
+        assert_eq!(snapshot["data"]["bids"][0]["quantity"], 1.0);
+
+        order_book.add_order(Order::new(2, 100.0, 2.0, OrderType::Limit, Side::Buy));
+        symbol_market.write().unwrap().apply_depth(&order_book.take_depth_changes(), 6);
+        let update = recv_json(&mut client).await;
+        assert_eq!(update["kind"], "update");
+        assert_eq!(update["seq_id"], 6);
+        assert_eq!(update["data"]["bids"][0]["quantity"], 3.0);
+
+        // 视图重置后重新发送快照
+        symbol_market.write().unwrap().reset(&order_book, 10, 6);
+        let snapshot = recv_json(&mut client).await;
+        assert_eq!(snapshot["kind"], "snapshot");
+        assert_eq!(snapshot["seq_id"], 6);
+    }
+}